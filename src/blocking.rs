@@ -0,0 +1,270 @@
+//! A blocking (synchronous) facade over the asynchronous [`crate::Client`], for use in
+//! scripts or applications which don't run inside of an async runtime.
+//!
+//! Each blocking client drives the asynchronous client on its own single-threaded runtime,
+//! in the same way as [`reqwest::blocking`](https://docs.rs/reqwest/latest/reqwest/blocking/).
+//!
+//! The blocking client **must not** be used from within an async runtime, as blocking on the
+//! runtime will panic. Use [`crate::Client`] directly instead.
+
+use crate::actions::Action as AsyncAction;
+use crate::authentication::{ChallengeResponse, LoginOutcome, TrustedDevice, User};
+use crate::devices::Device;
+use crate::products::{Product as AsyncProduct, States};
+use crate::weather::Weather;
+use crate::{ApiError, AuthenticationError};
+use std::ops::{Deref, DerefMut};
+use std::time::Duration;
+use tokio::runtime::Runtime;
+
+/// A blocking client used to authenticate and interact with Hive.
+///
+/// See [`crate::Client`] for the asynchronous equivalent of each method.
+#[derive(Debug)]
+pub struct Client {
+    inner: crate::Client,
+    runtime: Runtime,
+}
+
+impl Client {
+    /// Create a new blocking client.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// let client = hive_client::blocking::Client::new("Home Automation");
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics if the runtime used to drive the client could not be created.
+    #[must_use]
+    pub fn new(friendly_name: &str) -> Self {
+        Self {
+            inner: crate::Client::new(friendly_name),
+            runtime: tokio::runtime::Builder::new_current_thread()
+                .enable_all()
+                .build()
+                .expect("Runtime should be created for the blocking client"),
+        }
+    }
+
+    /// Login to Hive as a User.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use hive_client::authentication::{TrustedDevice, User};
+    ///
+    /// let client = hive_client::blocking::Client::new("Home Automation");
+    ///
+    /// let trusted_device = Some(TrustedDevice::new(
+    ///     "device_password",
+    ///     "device_group_key",
+    ///     "device_key"
+    /// ));
+    ///
+    /// client.login(User::new("example@example.com", "example"), trusted_device)
+    ///     .expect("Login should succeed");
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// See [`crate::Client::login`].
+    // Mirrors the error returned by the asynchronous client.
+    #[allow(clippy::result_large_err)]
+    pub fn login(
+        &self,
+        user: User,
+        trusted_device: Option<TrustedDevice>,
+    ) -> Result<LoginOutcome, AuthenticationError> {
+        self.runtime
+            .block_on(self.inner.login(user, trusted_device))
+    }
+
+    /// Respond to a challenge issued by Hive during the authentication process.
+    ///
+    /// # Errors
+    ///
+    /// See [`crate::Client::respond_to_challenge`].
+    // Mirrors the error returned by the asynchronous client.
+    #[allow(clippy::result_large_err)]
+    pub fn respond_to_challenge(
+        &mut self,
+        challenge_response: ChallengeResponse,
+    ) -> Result<LoginOutcome, AuthenticationError> {
+        self.runtime
+            .block_on(self.inner.respond_to_challenge(challenge_response))
+    }
+
+    /// Logout from Hive.
+    ///
+    /// See [`crate::Client::logout`].
+    pub fn logout(&mut self) {
+        self.runtime.block_on(self.inner.logout());
+    }
+
+    /// Get the products currently running in the Hive account.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use hive_client::authentication::{TrustedDevice, User};
+    /// use hive_client::products::{ProductData, State, States};
+    ///
+    /// let client = hive_client::blocking::Client::new("Home Automation");
+    ///
+    /// let trusted_device = Some(TrustedDevice::new(
+    ///     "device_password",
+    ///     "device_group_key",
+    ///     "device_key"
+    /// ));
+    ///
+    /// client.login(User::new("example@example.com", "example"), trusted_device)
+    ///     .expect("Login should succeed");
+    ///
+    /// let products = client.get_products()
+    ///     .expect("Products should be retrieved");
+    ///
+    /// if let Some(mut heating) = products.into_iter().find(|product| matches!(product.data, ProductData::Heating { .. })) {
+    ///     heating.set_state(&States(vec![State::TargetTemperature(18.0)]))
+    ///         .expect("Target temperature should be set");
+    /// }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// See [`crate::Client::get_products`].
+    pub fn get_products(&self) -> Result<Vec<Product<'_>>, ApiError> {
+        Ok(self
+            .runtime
+            .block_on(self.inner.get_products())?
+            .into_iter()
+            .map(|inner| Product {
+                inner,
+                runtime: &self.runtime,
+            })
+            .collect())
+    }
+
+    /// Get the devices setup in the Hive account.
+    ///
+    /// # Errors
+    ///
+    /// See [`crate::Client::get_devices`].
+    pub fn get_devices(&self) -> Result<Vec<Device>, ApiError> {
+        self.runtime.block_on(self.inner.get_devices())
+    }
+
+    /// Get the Quick Actions setup in the Hive account.
+    ///
+    /// # Errors
+    ///
+    /// See [`crate::Client::get_actions`].
+    pub fn get_actions(&self) -> Result<Vec<Action<'_>>, ApiError> {
+        Ok(self
+            .runtime
+            .block_on(self.inner.get_actions())?
+            .into_iter()
+            .map(|inner| Action {
+                inner,
+                runtime: &self.runtime,
+            })
+            .collect())
+    }
+
+    /// Get the current weather according to Hive, for a given postcode.
+    ///
+    /// # Errors
+    ///
+    /// See [`crate::Client::get_weather`].
+    pub fn get_weather(&self, postcode: &str) -> Result<Weather, ApiError> {
+        self.runtime.block_on(self.inner.get_weather(postcode))
+    }
+}
+
+/// A Product which is enabled in a Hive account, returned by the blocking [`Client`].
+///
+/// The data about the product can be read directly, as with [`crate::products::Product`].
+#[derive(Debug)]
+pub struct Product<'a> {
+    inner: AsyncProduct<'a>,
+    runtime: &'a Runtime,
+}
+
+impl<'a> Deref for Product<'a> {
+    type Target = AsyncProduct<'a>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.inner
+    }
+}
+
+impl DerefMut for Product<'_> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.inner
+    }
+}
+
+impl Product<'_> {
+    /// Set the state of the product.
+    ///
+    /// # Errors
+    ///
+    /// See [`crate::products::Product::set_state`].
+    pub fn set_state(&mut self, states: &States) -> Result<bool, ApiError> {
+        self.runtime.block_on(self.inner.set_state(states))
+    }
+}
+
+/// A Quick Action setup in a Hive account, returned by the blocking [`Client`].
+#[derive(Debug)]
+pub struct Action<'a> {
+    inner: AsyncAction<'a>,
+    runtime: &'a Runtime,
+}
+
+impl<'a> Deref for Action<'a> {
+    type Target = AsyncAction<'a>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.inner
+    }
+}
+
+impl Action<'_> {
+    /// Activate the Quick Action.
+    ///
+    /// # Errors
+    ///
+    /// See [`crate::actions::Action::activate`].
+    pub fn activate(&self) -> Result<bool, ApiError> {
+        self.runtime.block_on(self.inner.activate())
+    }
+
+    /// Activate the Quick Action, then wait until its products reflect the change.
+    ///
+    /// # Errors
+    ///
+    /// See [`crate::actions::Action::activate_and_confirm`].
+    pub fn activate_and_confirm(&self, timeout: Duration) -> Result<bool, ApiError> {
+        self.runtime
+            .block_on(self.inner.activate_and_confirm(timeout))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::RefreshError;
+
+    #[test]
+    fn test_blocking_client_requires_a_login() {
+        let client = Client::new("Home Automation");
+
+        assert!(matches!(
+            client.get_products(),
+            Err(ApiError::RefreshError(RefreshError::NotLoggedIn))
+        ));
+    }
+}
@@ -0,0 +1,16 @@
+use crate::actions::Action;
+use crate::devices::Device;
+use crate::products::Product;
+
+/// Everything set up in a Hive account, as returned by [`crate::Client::get_all`].
+#[derive(Debug)]
+pub struct Account<'a> {
+    /// The products in the Hive account (see [`crate::Client::get_products`]).
+    pub products: Vec<Product<'a>>,
+
+    /// The devices in the Hive account (see [`crate::Client::get_devices`]).
+    pub devices: Vec<Device>,
+
+    /// The Quick Actions in the Hive account (see [`crate::Client::get_actions`]).
+    pub actions: Vec<Action<'a>>,
+}
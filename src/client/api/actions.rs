@@ -1,15 +1,20 @@
 use crate::Client;
-use crate::client::api::HiveApi;
 use crate::client::api::error::ApiError;
+#[cfg(feature = "fixtures")]
+use crate::client::api::fixtures::RecordedCall;
+use crate::client::api::{Correlated, HiveApi, ensure_authorized, send_correlated};
 use crate::client::authentication::Tokens;
+use crate::helper::timestamp;
 use crate::helper::url::{Url, get_base_url};
-use chrono::{DateTime, Utc, serde::ts_milliseconds};
+use crate::products::{ProductType, States};
+use chrono::{DateTime, Utc};
 use reqwest::StatusCode;
 use serde::Deserialize;
 use serde_json::Value;
 use std::collections::HashMap;
 use std::fmt;
 use std::fmt::{Debug, Formatter};
+use std::time::Duration;
 
 #[derive(Deserialize, Debug)]
 #[non_exhaustive]
@@ -25,9 +30,11 @@ pub struct ActionData {
     pub enabled: bool,
 
     /// The template used for the Quick Action.
+    ///
+    /// See [`ActionData::operations`] for what the Quick Action does when it's activated.
     pub template: String,
 
-    #[serde(with = "ts_milliseconds")]
+    #[serde(with = "timestamp")]
     #[serde(rename = "created")]
     /// The date and time when the Quick Action was first created.
     pub created_at: DateTime<Utc>,
@@ -37,6 +44,77 @@ pub struct ActionData {
     pub extra: HashMap<String, Value>,
 }
 
+/// How often the products are checked while waiting for a Quick Action to take effect (see
+/// [`Action::activate_and_confirm`]).
+const CONFIRMATION_POLL_INTERVAL: Duration = Duration::from_secs(2);
+
+#[derive(Debug, Clone)]
+#[non_exhaustive]
+/// An operation performed by a Quick Action when it is activated - for example, turning a
+/// Heating product off.
+///
+/// Hive does not document the operations, so each is parsed as far as possible, with the
+/// original operation kept in [`ActionOperation::raw`].
+pub struct ActionOperation {
+    /// The unique ID of the product the operation changes.
+    pub product_id: Option<String>,
+
+    /// The type of the product the operation changes.
+    pub product_type: Option<ProductType>,
+
+    /// The states the operation sets on the product.
+    pub states: Option<States>,
+
+    /// The operation, as it was returned by the Hive API.
+    pub raw: Value,
+}
+
+impl From<&Value> for ActionOperation {
+    fn from(raw: &Value) -> Self {
+        Self {
+            product_id: raw
+                .get("id")
+                .and_then(Value::as_str)
+                .map(ToString::to_string),
+            product_type: raw
+                .get("type")
+                .and_then(Value::as_str)
+                .and_then(|r#type| r#type.parse().ok()),
+            states: raw
+                .get("value")
+                .and_then(|states| States::deserialize(states).ok()),
+            raw: raw.clone(),
+        }
+    }
+}
+
+impl ActionData {
+    /// The operations the Quick Action performs when it's activated, as reported in the
+    /// `actions` of the Quick Action.
+    ///
+    /// This is empty if Hive did not report the operations of the Quick Action.
+    #[must_use]
+    pub fn operations(&self) -> Vec<ActionOperation> {
+        self.extra
+            .get("actions")
+            .and_then(Value::as_array)
+            .into_iter()
+            .flatten()
+            .map(ActionOperation::from)
+            .collect()
+    }
+
+    /// The states the Quick Action applies to each of its products (by the product's ID).
+    ///
+    /// Any operations which couldn't be fully parsed are skipped.
+    fn effects(&self) -> Vec<(String, States)> {
+        self.operations()
+            .into_iter()
+            .filter_map(|operation| Some((operation.product_id?, operation.states?)))
+            .collect()
+    }
+}
+
 /// A [Quick Action](https://www.hivehome.com/ie/support/Help_Using_Hive/HUH_General/What-are-Quick-Actions) setup in the Hive account.
 pub struct Action<'a> {
     client: &'a Client,
@@ -97,6 +175,105 @@ impl Action<'_> {
     pub async fn activate(&self) -> Result<bool, ApiError> {
         self.client.activate_action(&self.data.id).await
     }
+
+    /// Activate the Quick Action, then wait until its products reflect the change.
+    ///
+    /// Hive accepts the activation straight away, but applies the Quick Action to its products
+    /// asynchronously - so products retrieved immediately afterwards may still show their
+    /// previous states. This checks the products every couple of seconds until each of them
+    /// reflects the states applied by the Quick Action.
+    ///
+    /// If Hive does not report the states the Quick Action applies, there is nothing to wait
+    /// for, so this returns as soon as the Quick Action is activated.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use hive_client::authentication::{TrustedDevice, User};
+    /// use std::time::Duration;
+    ///
+    /// # tokio_test::block_on(async {
+    /// let client = hive_client::Client::new("Home Automation");
+    ///
+    /// let trusted_device = Some(TrustedDevice::new(
+    ///     "device_password",
+    ///     "device_group_key",
+    ///     "device_key"
+    /// ));
+    ///
+    /// client.login(User::new("example@example.com", "example"), trusted_device)
+    ///     .await
+    ///     .expect("Login should succeed");
+    ///
+    /// let actions = client.get_actions()
+    ///     .await
+    ///     .expect("Quick action should be retrieved");
+    ///
+    /// let turn_off_heating = actions.into_iter()
+    ///     .find(|action| action.data.id == "1234-5678-000-0000")
+    ///     .expect("Quick action to turn off heating should exist");
+    ///
+    /// turn_off_heating.activate_and_confirm(Duration::from_secs(30))
+    ///     .await
+    ///     .expect("Heating should have been turned off");
+    /// # })
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the Quick Action could not be activated, or the products could not
+    /// be retrieved. Returns [`ApiError::ActionNotConfirmed`] if the products did not reflect
+    /// the change within the timeout.
+    pub async fn activate_and_confirm(&self, timeout: Duration) -> Result<bool, ApiError> {
+        if !self.activate().await? {
+            return Ok(false);
+        }
+
+        let effects = self.data.effects();
+
+        if effects.is_empty() {
+            return Ok(true);
+        }
+
+        let confirmation = async {
+            loop {
+                let products = Box::pin(self.client.get_products()).await?;
+
+                if effects.iter().all(|(id, states)| {
+                    products
+                        .iter()
+                        .any(|product| product.id() == id && product.reflects(states))
+                }) {
+                    return Ok(true);
+                }
+
+                tokio::time::sleep(CONFIRMATION_POLL_INTERVAL).await;
+            }
+        };
+
+        tokio::time::timeout(timeout, confirmation)
+            .await
+            .unwrap_or_else(|_| Err(ApiError::ActionNotConfirmed(self.data.id.clone())))
+    }
+
+    /// Activate the Quick Action, sending a caller-supplied correlation ID with the request.
+    ///
+    /// The correlation ID is returned alongside the ID Hive assigned to the request, so that
+    /// the activation can be traced between the caller's logs and Hive.
+    ///
+    /// See [`Action::activate`].
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the Quick Action could not be activated.
+    pub async fn activate_with_id(
+        &self,
+        correlation_id: &str,
+    ) -> Result<Correlated<bool>, ApiError> {
+        self.client
+            .activate_action_with_id(&self.data.id, Some(correlation_id))
+            .await
+    }
 }
 
 impl HiveApi {
@@ -104,6 +281,11 @@ impl HiveApi {
         &self,
         tokens: &Tokens,
     ) -> Result<Vec<ActionData>, ApiError> {
+        #[cfg(feature = "fixtures")]
+        if let Some(fixtures) = &self.fixtures {
+            return Ok(serde_json::from_value(fixtures.load("actions")?)?);
+        }
+
         let response = self
             .client
             .get(get_base_url(&Url::Actions {
@@ -114,28 +296,85 @@ impl HiveApi {
             .send()
             .await;
 
-        response?
-            .json::<Vec<ActionData>>()
-            .await
-            .map_err(ApiError::from)
+        self.decode_json(response?).await
     }
 
     pub(crate) async fn activate_action(
         &self,
         tokens: &Tokens,
         action_id: &str,
-    ) -> Result<bool, ApiError> {
-        let response = self
+        correlation_id: Option<&str>,
+    ) -> Result<Correlated<bool>, ApiError> {
+        #[cfg(feature = "fixtures")]
+        if let Some(fixtures) = &self.fixtures {
+            fixtures.record(RecordedCall::Activate {
+                action_id: action_id.to_string(),
+            });
+
+            return Ok(Correlated {
+                value: true,
+                correlation_id: correlation_id.map(ToString::to_string),
+                request_id: None,
+            });
+        }
+
+        let request = self
             .client
             .post(get_base_url(&Url::Actions {
                 id: Some(action_id),
                 activate: true,
             }))
             .body("{}")
-            .header("Authorization", &tokens.id_token)
-            .send()
-            .await?;
+            .header("Authorization", &tokens.id_token);
+
+        send_correlated(request, correlation_id)
+            .await?
+            .try_map(|response| Ok(ensure_authorized(response)?.status() == StatusCode::OK))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::products::tests::heating;
+    use crate::products::{Mode, State};
+
+    #[test]
+    fn test_operations_are_parsed_and_checked_against_products() {
+        let client = Client::new("Home Automation");
+
+        let action: ActionData = serde_json::from_str(
+            r#"{
+                "id": "action",
+                "name": "Heating Off",
+                "enabled": true,
+                "template": "heating-off",
+                "created": 0,
+                "actions": [
+                    { "id": "heating", "type": "heating", "value": { "mode": "OFF" } },
+                    { "id": "broken" }
+                ]
+            }"#,
+        )
+        .expect("Quick Action should deserialize");
+
+        let operations = action.operations();
+        assert_eq!(operations.len(), 2);
+        assert_eq!(operations[0].product_type, Some(ProductType::Heating));
+        assert!(operations[1].product_id.is_some());
+        assert!(operations[1].states.is_none());
+
+        let effects = action.effects();
+        assert_eq!(effects.len(), 1);
+        assert_eq!(effects[0].0, "heating");
+
+        let heating = heating(
+            &client,
+            serde_json::json!({ "online": true, "working": true }),
+            serde_json::json!({ "mode": "OFF", "target": 7.0 }),
+        );
 
-        Ok(response.status() == StatusCode::OK)
+        assert!(heating.reflects(&effects[0].1));
+        assert!(!heating.reflects(&States(vec![State::Mode(Mode::Manual)])));
     }
 }
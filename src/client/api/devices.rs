@@ -3,14 +3,19 @@ use std::collections::HashMap;
 use crate::client::api::HiveApi;
 use crate::client::api::error::ApiError;
 use crate::client::authentication::Tokens;
-use crate::helper::url::{Url, get_base_url};
-use chrono::{DateTime, Utc, serde::ts_milliseconds};
+use crate::helper::timestamp;
+use crate::helper::url::Url;
+use chrono::{DateTime, Utc};
 use serde::Deserialize;
+use serde::de::DeserializeOwned;
 use serde_json::Value;
+use std::fmt;
+use std::fmt::{Display, Formatter};
 
 #[derive(Deserialize, Debug)]
 #[serde(rename_all = "lowercase")]
 #[allow(missing_docs)]
+#[non_exhaustive]
 pub enum PowerType {
     /// The device is powered by an internal battery.
     Battery,
@@ -38,11 +43,47 @@ pub struct Properties {
     /// The ID of the zone the device is located in (if applicable).
     pub zone_id: Option<String>,
 
+    #[serde(default)]
+    /// The model identifier of the device (for example, `SLT3` for a Thermostat), if it is
+    /// reported. See [`Properties::friendly_model_name`].
+    pub model: Option<String>,
+
     #[serde(flatten)]
     #[allow(missing_docs)]
     pub extra: HashMap<String, Value>,
 }
 
+impl Properties {
+    /// The model identifiers of known Hive devices, and the names they are sold under.
+    const MODEL_NAMES: &[(&str, &str)] = &[
+        ("SLR1", "Hive Receiver (single channel)"),
+        ("SLR2", "Hive Receiver (dual channel)"),
+        ("SLT3", "Hive Thermostat"),
+        ("SLT3B", "Hive Thermostat"),
+        ("SLT3C", "Hive Thermostat"),
+        ("SLT5", "Hive Thermostat Mini"),
+        ("NANO2", "Hive Hub"),
+        ("HUB360", "Hive Hub 360"),
+    ];
+
+    /// A recognisable name for the model of the device (for example, "Hive Thermostat" for
+    /// an `SLT3`), falling back to the raw [`Properties::model`] for models which are not
+    /// known to the crate.
+    ///
+    /// Returns `None` if the device did not report its model.
+    #[must_use]
+    pub fn friendly_model_name(&self) -> Option<&str> {
+        let model = self.model.as_deref()?;
+
+        Some(
+            Self::MODEL_NAMES
+                .iter()
+                .find(|(id, _)| id.eq_ignore_ascii_case(model))
+                .map_or(model, |(_, name)| name),
+        )
+    }
+}
+
 #[derive(Deserialize, Debug)]
 #[serde(rename_all = "camelCase")]
 #[non_exhaustive]
@@ -63,11 +104,11 @@ pub struct Thermostat {
     /// The unique ID of the Thermostat.
     pub id: String,
 
-    #[serde(with = "ts_milliseconds")]
+    #[serde(with = "timestamp")]
     /// The date and time the Thermostat last communicated with the Hub.
     pub last_seen: DateTime<Utc>,
 
-    #[serde(with = "ts_milliseconds")]
+    #[serde(with = "timestamp")]
     #[serde(rename = "created")]
     /// The date and time when the Thermostat was first created.
     pub created_at: DateTime<Utc>,
@@ -92,11 +133,11 @@ pub struct Hub {
     /// The unique ID of the Hub.
     pub id: String,
 
-    #[serde(with = "ts_milliseconds")]
+    #[serde(with = "timestamp")]
     /// The date and time the Hub last communicated with the Hive servers.
     pub last_seen: DateTime<Utc>,
 
-    #[serde(with = "ts_milliseconds")]
+    #[serde(with = "timestamp")]
     #[serde(rename = "created")]
     /// The date and time when the Hub was first created.
     pub created_at: DateTime<Utc>,
@@ -113,6 +154,27 @@ pub struct Hub {
     pub extra: HashMap<String, Value>,
 }
 
+impl Hub {
+    /// Whether the Hub is connected to the Hive servers.
+    ///
+    /// Every other device in the account reaches the Hive servers through the Hub, so when
+    /// this is `false` the state of every other device is likely to be stale.
+    #[must_use]
+    pub const fn is_connected_to_cloud(&self) -> bool {
+        self.properties.is_online
+    }
+
+    /// The type of connection the Hub is using to reach the internet (for example, `ETHERNET`
+    /// or `WIFI`), if it is reported.
+    #[must_use]
+    pub fn connection(&self) -> Option<&str> {
+        self.properties
+            .extra
+            .get("connection")
+            .and_then(Value::as_str)
+    }
+}
+
 #[derive(Deserialize, Debug)]
 #[serde(rename_all = "camelCase")]
 #[non_exhaustive]
@@ -121,11 +183,11 @@ pub struct BoilerModule {
     /// The unique ID of the Boiler Module.
     pub id: String,
 
-    #[serde(with = "ts_milliseconds")]
+    #[serde(with = "timestamp")]
     /// The date and time the Boiler Module last communicated with the Hub.
     pub last_seen: DateTime<Utc>,
 
-    #[serde(with = "ts_milliseconds")]
+    #[serde(with = "timestamp")]
     #[serde(rename = "created")]
     /// The date and time when the Boiler Module was first created.
     pub created_at: DateTime<Utc>,
@@ -142,6 +204,22 @@ pub struct BoilerModule {
     pub extra: HashMap<String, Value>,
 }
 
+impl BoilerModule {
+    /// Whether the relay of the Boiler Module is currently calling the boiler on.
+    ///
+    /// This is the authoritative signal of whether the boiler is running, rather than the
+    /// demand reported by the Thermostat. Returns `false` if the Boiler Module did not report
+    /// the state of its relay (for example, while it is offline).
+    #[must_use]
+    pub fn is_calling_for_heat(&self) -> bool {
+        self.properties
+            .extra
+            .get("working")
+            .and_then(Value::as_bool)
+            .unwrap_or(false)
+    }
+}
+
 #[derive(Deserialize, Debug)]
 #[serde(rename_all = "lowercase")]
 #[serde(tag = "type")]
@@ -163,6 +241,119 @@ pub enum DeviceData {
     Unknown,
 }
 
+impl DeviceData {
+    /// The type tags (as reported by the Hive API) of the devices which are mapped by the
+    /// crate. Devices of any other type are returned as [`DeviceData::Unknown`].
+    pub const SUPPORTED_TYPES: &[&str] = &["thermostatui", "hub", "boilermodule"];
+
+    /// Decode a device returned by the Hive API, rejecting any devices which are yet to be
+    /// mapped by the crate.
+    pub(crate) fn decode_known(raw: Value) -> Result<Self, ApiError> {
+        match Self::deserialize(&raw)? {
+            Self::Unknown => Err(ApiError::UnknownDeviceType { raw }),
+            data => Ok(data),
+        }
+    }
+
+    /// The type of the device, as named by the Hive API.
+    pub(crate) const fn r#type(&self) -> &'static str {
+        match self {
+            Self::Thermostat(_) => "thermostatui",
+            Self::Hub(_) => "hub",
+            Self::BoilerModule(_) => "boilermodule",
+            Self::Unknown => "unknown",
+        }
+    }
+
+    /// The ID and name of the zone the device is located in, if it is in a zone.
+    pub(crate) fn zone(&self) -> Option<(&str, &str)> {
+        let (Self::Thermostat(Thermostat {
+            properties, state, ..
+        })
+        | Self::Hub(Hub {
+            properties, state, ..
+        })
+        | Self::BoilerModule(BoilerModule {
+            properties, state, ..
+        })) = self
+        else {
+            return None;
+        };
+
+        Some((properties.zone_id.as_deref()?, state.zone_name.as_deref()?))
+    }
+}
+
+impl TryFrom<DeviceData> for Thermostat {
+    type Error = ApiError;
+
+    fn try_from(data: DeviceData) -> Result<Self, Self::Error> {
+        match data {
+            DeviceData::Thermostat(thermostat) => Ok(thermostat),
+            data => Err(ApiError::UnsupportedDevice(data.r#type().to_string())),
+        }
+    }
+}
+
+impl TryFrom<DeviceData> for Hub {
+    type Error = ApiError;
+
+    fn try_from(data: DeviceData) -> Result<Self, Self::Error> {
+        match data {
+            DeviceData::Hub(hub) => Ok(hub),
+            data => Err(ApiError::UnsupportedDevice(data.r#type().to_string())),
+        }
+    }
+}
+
+impl TryFrom<DeviceData> for BoilerModule {
+    type Error = ApiError;
+
+    fn try_from(data: DeviceData) -> Result<Self, Self::Error> {
+        match data {
+            DeviceData::BoilerModule(boiler_module) => Ok(boiler_module),
+            data => Err(ApiError::UnsupportedDevice(data.r#type().to_string())),
+        }
+    }
+}
+
+impl Display for DeviceData {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let (kind, id, properties, state) = match self {
+            Self::Thermostat(Thermostat {
+                id,
+                properties,
+                state,
+                ..
+            }) => ("Thermostat", id, properties, state),
+            Self::Hub(Hub {
+                id,
+                properties,
+                state,
+                ..
+            }) => ("Hub", id, properties, state),
+            Self::BoilerModule(BoilerModule {
+                id,
+                properties,
+                state,
+                ..
+            }) => ("Boiler Module", id, properties, state),
+            Self::Unknown => return write!(f, "Unknown device"),
+        };
+
+        write!(
+            f,
+            "{kind} \"{}\" (id={id}, {})",
+            state.name,
+            if properties.is_online {
+                "online"
+            } else {
+                "offline"
+            }
+        )
+    }
+}
+
 /// A Device setup in a Hive account.
 ///
 /// For example, a [`DeviceData::Thermostat`], a [`DeviceData::Hub`], etc.
@@ -178,17 +369,135 @@ impl Device {
     }
 }
 
+/// The keys Hive may wrap the list of devices under, rather than returning a bare array.
+const LIST_KEYS: &[&str] = &["devices", "data"];
+
 impl HiveApi {
-    pub(crate) async fn get_devices(&self, tokens: &Tokens) -> Result<Vec<DeviceData>, ApiError> {
-        let response = self
-            .client
-            .get(get_base_url(&Url::Device))
-            .header("Authorization", &tokens.id_token)
-            .send()
-            .await;
+    /// Retrieve the devices in the Hive account, decoding each as the given type - for
+    /// example, as [`DeviceData`] or as the raw JSON returned by the Hive API.
+    pub(crate) async fn get_devices<T: DeserializeOwned>(
+        &self,
+        tokens: &Tokens,
+    ) -> Result<Vec<T>, ApiError> {
+        self.fetch_list(tokens, &Url::Device, "devices", LIST_KEYS)
+            .await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_hub_connectivity() {
+        let hub: DeviceData = serde_json::from_str(
+            r#"{
+                "type": "hub",
+                "id": "hub",
+                "lastSeen": 0,
+                "created": 0,
+                "props": { "online": false, "power": "mains", "connection": "ETHERNET" },
+                "state": { "name": "Hub" }
+            }"#,
+        )
+        .expect("Device should deserialize");
+
+        let DeviceData::Hub(hub) = hub else {
+            panic!("Expected a hub")
+        };
+
+        assert!(!hub.is_connected_to_cloud());
+        assert_eq!(hub.connection(), Some("ETHERNET"));
+    }
 
-        let body = response?.text().await?;
+    #[test]
+    fn test_friendly_model_name() {
+        for (model, expected) in [
+            (r#", "model": "SLT3b""#, Some("Hive Thermostat")),
+            (r#", "model": "SLT9""#, Some("SLT9")),
+            ("", None),
+        ] {
+            let thermostat: Thermostat = serde_json::from_str(&format!(
+                r#"{{
+                    "id": "thermostat",
+                    "lastSeen": 0,
+                    "created": 0,
+                    "props": {{ "online": true{model} }},
+                    "state": {{ "name": "Thermostat" }}
+                }}"#
+            ))
+            .expect("Thermostat should deserialize");
+
+            assert_eq!(
+                thermostat.properties.friendly_model_name(),
+                expected,
+                "{model:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_boiler_module_relay_state() {
+        for (props, expected) in [
+            (r#"{ "online": true, "working": true }"#, true),
+            (r#"{ "online": true, "working": false }"#, false),
+            (r#"{ "online": false }"#, false),
+        ] {
+            let boiler_module: DeviceData = serde_json::from_str(&format!(
+                r#"{{
+                    "type": "boilermodule",
+                    "id": "boiler-module",
+                    "lastSeen": 0,
+                    "created": 0,
+                    "props": {props},
+                    "state": {{ "name": "Boiler Module" }}
+                }}"#
+            ))
+            .expect("Device should deserialize");
+
+            let DeviceData::BoilerModule(boiler_module) = boiler_module else {
+                panic!("Expected a boiler module")
+            };
+
+            assert_eq!(boiler_module.is_calling_for_heat(), expected, "{props}");
+        }
+    }
+
+    #[test]
+    fn test_supported_types_are_decoded() {
+        for r#type in DeviceData::SUPPORTED_TYPES {
+            let data = DeviceData::deserialize(&serde_json::json!({
+                "type": r#type,
+                "id": "1234",
+                "lastSeen": 0,
+                "created": 0,
+                "props": { "online": true },
+                "state": { "name": "Device" }
+            }))
+            .expect("Device should deserialize");
+
+            assert_eq!(data.r#type(), *r#type);
+        }
+    }
 
-        Ok(serde_json::from_str(&body)?)
+    #[test]
+    fn test_concrete_devices_can_be_extracted() {
+        let hub: DeviceData = serde_json::from_str(
+            r#"{
+                "type": "hub",
+                "id": "hub",
+                "lastSeen": 0,
+                "created": 0,
+                "props": { "online": true },
+                "state": { "name": "Hub" }
+            }"#,
+        )
+        .expect("Device should deserialize");
+
+        assert!(matches!(
+            Thermostat::try_from(hub),
+            Err(ApiError::UnsupportedDevice(r#type)) if r#type == "hub"
+        ));
+        assert!(Hub::try_from(DeviceData::Unknown).is_err());
     }
 }
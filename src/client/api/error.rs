@@ -1,4 +1,5 @@
-use crate::RefreshError;
+use crate::products::State;
+use crate::{AuthenticationError, RefreshError};
 use thiserror::Error;
 
 #[derive(Error, Debug)]
@@ -14,7 +15,131 @@ pub enum ApiError {
     /// The response from the Hive API was valid, but could not be decoded.
     InvalidResponse(#[from] serde_json::Error),
 
+    #[error("The Hive API rejected the authentication tokens with status {0}")]
+    /// The Hive API did not accept the authentication tokens sent with the request.
+    Unauthorized(reqwest::StatusCode),
+
+    #[error("The Hive API returned an unexpected status {0}")]
+    /// The Hive API returned a response which was not successful.
+    UnexpectedStatus(reqwest::StatusCode),
+
+    #[error("The response from the Hive API is larger than the maximum of {0} bytes")]
+    /// The response from the Hive API was larger than the maximum response size (see
+    /// [`crate::Client::with_max_response_size`]), so was not read.
+    ResponseTooLarge(usize),
+
+    #[error("The Hive API returned a product which is yet to be mapped by the crate")]
+    /// The Hive API returned a product which is yet to be mapped by the crate, and the client
+    /// was configured to reject them (see [`crate::Client::fail_on_unknown`]).
+    UnknownProductType {
+        /// The product, as returned by the Hive API.
+        raw: serde_json::Value,
+    },
+
+    #[error("The Hive API returned a device which is yet to be mapped by the crate")]
+    /// The Hive API returned a device which is yet to be mapped by the crate, and the client
+    /// was configured to reject them (see [`crate::Client::fail_on_unknown`]).
+    UnknownDeviceType {
+        /// The device, as returned by the Hive API.
+        raw: serde_json::Value,
+    },
+
+    #[error("The product {0} could not be found in the Hive account")]
+    /// A product with the given ID could not be found in the Hive account.
+    ProductNotFound(String),
+
+    #[error("The operation is not supported by {0} products")]
+    /// The operation is not supported by the type of product it was used on - for example,
+    /// turning a Heating product's hot water on.
+    UnsupportedProduct(String),
+
+    #[error("The operation is not supported by {0} devices")]
+    /// The operation is not supported by the type of device it was used on - for example,
+    /// converting a Hub into a Thermostat.
+    UnsupportedDevice(String),
+
+    #[error("The product is not following a schedule")]
+    /// The operation requires the product to be following a schedule (in
+    /// [`crate::products::Mode::Schedule`]), but it is not - or has no schedule to follow.
+    NotScheduled,
+
+    #[cfg(feature = "fixtures")]
+    #[error("The recorded response could not be loaded: {0}")]
+    /// A recorded response (for a [`crate::Client`] created with
+    /// [`crate::Client::from_fixtures`]) was missing, or could not be read.
+    FixtureUnavailable(String),
+
+    #[error("The Quick Action {0} did not take effect before the timeout")]
+    /// The Quick Action was activated, but its products did not reflect the change before the
+    /// timeout given to [`crate::actions::Action::activate_and_confirm`].
+    ActionNotConfirmed(String),
+
+    #[error("The product {0} is offline")]
+    /// The product was offline when it was last retrieved, so a change to its states would
+    /// not take effect. Only returned when [`crate::Client::reject_offline_changes`] is enabled.
+    DeviceOffline(String),
+
+    #[error("The state {state:?} is not applicable to {product_type} products")]
+    /// One of the states being set is not applicable to the type of product - for example,
+    /// setting [`State::TargetTemperature`] on a Hot Water product.
+    InvalidStateForProduct {
+        /// The state which is not applicable to the product.
+        state: Box<State>,
+
+        /// The type of the product the state was being set on.
+        product_type: String,
+    },
+
+    #[error("The states conflict with each other: {0}")]
+    /// The states being set conflict with each other - for example, a boost along with a mode
+    /// other than [`crate::products::Mode::Boost`].
+    ConflictingStates(String),
+
+    #[error("The target temperature {0} is outside of the range supported by Hive")]
+    /// The target temperature requested is outside of the range supported by Hive
+    /// ([`crate::products::MIN_TARGET_TEMPERATURE`] to [`crate::products::MAX_TARGET_TEMPERATURE`]).
+    InvalidTargetTemperature(f32),
+
+    #[error("The boost duration ({0:?}) is not one of the durations supported by Hive")]
+    /// The length of time requested for a boost is not one of the durations supported by Hive
+    /// (see [`crate::products::BoostDuration`]).
+    InvalidBoostDuration(std::time::Duration),
+
+    #[error("The end of the boost ({0}) is not in the future, or is too far away to boost until")]
+    /// The date and time a boost was requested to end at is not in the future, or is further
+    /// away than the longest boost Hive will accept (see [`crate::products::BoostDuration`]).
+    InvalidBoostEnd(chrono::DateTime<chrono::Utc>),
+
+    #[error("The time zone of the Hive account ({0:?}) is missing or not recognised")]
+    /// The time zone of the Hive account is either not set, or is not a recognised IANA time
+    /// zone name.
+    UnknownTimezone(Option<String>),
+
+    #[error("The call to the Hive API was cancelled")]
+    /// The call to the Hive API was cancelled, with a
+    /// [`CancellationToken`](tokio_util::sync::CancellationToken), before it completed.
+    Cancelled,
+
+    #[error("Hive has no weather available for the postcode {postcode}")]
+    /// Hive's weather provider has no weather for the postcode - for example, because the
+    /// location isn't covered by the provider.
+    WeatherUnavailable {
+        /// The postcode the weather was requested for.
+        postcode: String,
+    },
+
     #[error("An error occurred while trying to refresh the authentication tokens")]
     /// When refreshing the authentication tokens an error occurred.
     RefreshError(#[from] RefreshError),
+
+    #[error("An error occurred while communicating with the Hive authentication servers")]
+    /// A request made to the Hive authentication servers, on behalf of an already authenticated
+    /// user, failed.
+    AuthenticationError(#[source] Box<AuthenticationError>),
+}
+
+impl From<AuthenticationError> for ApiError {
+    fn from(error: AuthenticationError) -> Self {
+        Self::AuthenticationError(Box::new(error))
+    }
 }
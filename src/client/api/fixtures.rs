@@ -0,0 +1,292 @@
+use crate::client::api::error::ApiError;
+use crate::products::{ProductData, States};
+use serde::Deserialize;
+use serde_json::Value;
+use std::path::PathBuf;
+use std::sync::{Mutex, PoisonError};
+
+#[derive(Debug, Clone)]
+#[non_exhaustive]
+/// A change requested from a [`crate::Client`] created with [`crate::Client::from_fixtures`],
+/// which was recorded in place of being sent to Hive.
+pub enum RecordedCall {
+    /// States were set on a product (for example, with [`crate::products::Product::set_state`]).
+    SetState {
+        /// The unique ID of the product.
+        product_id: String,
+
+        /// The type of the product, as named by the Hive API.
+        product_type: String,
+
+        /// The states which were set on the product.
+        states: States,
+    },
+
+    /// A Quick Action was activated (for example, with [`crate::actions::Action::activate`]).
+    Activate {
+        /// The unique ID of the Quick Action.
+        action_id: String,
+    },
+
+    /// A raw JSON body was posted to the Hive API (with [`crate::Client::post_raw`]), which
+    /// returns `null` in place of the response.
+    Post {
+        /// The path the body was posted to.
+        path: String,
+
+        /// The body which was posted.
+        body: Value,
+    },
+}
+
+/// Recorded responses from the Hive API, served from a directory of JSON files in place of
+/// the Hive API.
+///
+/// The directory holds a file for each of the responses:
+///
+/// - `products.json` - The products, returned by [`crate::Client::get_products`].
+/// - `devices.json` - The devices, returned by [`crate::Client::get_devices`].
+/// - `actions.json` - The Quick Actions, returned by [`crate::Client::get_actions`].
+/// - `weather.json` - The weather, returned by [`crate::Client::get_weather`] (for any
+///   postcode).
+/// - `usage.json` - The usage history, returned by [`crate::products::Product::get_usage`]
+///   (for any product).
+/// - `raw/<path>.json` - The response to [`crate::Client::get_raw`] for the given path (for
+///   example, `raw/nodes/heating.json`).
+///
+/// Any response which isn't recorded is reported as [`ApiError::FixtureUnavailable`], rather
+/// than being requested from Hive.
+#[derive(Debug)]
+pub struct Fixtures {
+    directory: PathBuf,
+    calls: Mutex<Vec<RecordedCall>>,
+}
+
+impl Fixtures {
+    pub(crate) const fn new(directory: PathBuf) -> Self {
+        Self {
+            directory,
+            calls: Mutex::new(Vec::new()),
+        }
+    }
+
+    /// Read the recorded response with the given name.
+    pub(crate) fn load(&self, name: &str) -> Result<Value, ApiError> {
+        let path = self.directory.join(format!("{name}.json"));
+
+        let contents = std::fs::read_to_string(&path)
+            .map_err(|err| ApiError::FixtureUnavailable(format!("{}: {err}", path.display())))?;
+
+        Ok(serde_json::from_str(&contents)?)
+    }
+
+    pub(crate) fn record(&self, call: RecordedCall) {
+        self.calls
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .push(call);
+    }
+
+    /// The changes which have been recorded so far, in the order they were requested.
+    pub(crate) fn calls(&self) -> Vec<RecordedCall> {
+        self.calls
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .clone()
+    }
+
+    /// Record the states being set on a product, returning the product (found in the given
+    /// recorded products) with the states applied - as the Hive API would return the updated
+    /// product.
+    pub(crate) fn set_state_returning(
+        &self,
+        products: Value,
+        id: &str,
+        r#type: &str,
+        states: &States,
+    ) -> Result<ProductData, ApiError> {
+        self.record(RecordedCall::SetState {
+            product_id: id.to_string(),
+            product_type: r#type.to_string(),
+            states: states.clone(),
+        });
+
+        let Value::Array(products) = products else {
+            return Err(ApiError::FixtureUnavailable("products".to_string()));
+        };
+
+        let mut product = products
+            .into_iter()
+            .find(|product| product.get("id").and_then(Value::as_str) == Some(id))
+            .ok_or_else(|| ApiError::FixtureUnavailable(format!("products: {id}")))?;
+
+        if let (Some(Value::Object(state)), Value::Object(changes)) =
+            (product.get_mut("state"), serde_json::to_value(states)?)
+        {
+            state.extend(changes);
+        }
+
+        Ok(ProductData::deserialize(product)?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Client;
+    use crate::products::{Mode, State};
+
+    fn client() -> Client {
+        Client::from_fixtures(concat!(env!("CARGO_MANIFEST_DIR"), "/fixtures"))
+    }
+
+    #[tokio::test]
+    async fn test_recorded_responses_are_served_without_a_login() {
+        let client = client();
+
+        assert_eq!(
+            client
+                .get_products()
+                .await
+                .expect("Products should be read from the fixtures")
+                .len(),
+            2
+        );
+        assert_eq!(
+            client
+                .get_devices()
+                .await
+                .expect("Devices should be read from the fixtures")
+                .len(),
+            2
+        );
+        assert!(
+            client
+                .get_weather("SW1A 1AA")
+                .await
+                .expect("Weather should be read from the fixtures")
+                .data
+                .feels_like
+                .is_some()
+        );
+    }
+
+    #[tokio::test]
+    async fn test_changes_are_recorded() {
+        let client = client();
+
+        let mut products = client
+            .get_products()
+            .await
+            .expect("Products should be read from the fixtures");
+
+        let updated = products[0]
+            .set_state_returning(&States(vec![State::Mode(Mode::Off)]))
+            .await
+            .expect("State change should be recorded");
+
+        assert!(matches!(
+            updated,
+            ProductData::Heating(heating)
+                if heating.state.iter().any(|state| matches!(state, State::Mode(Mode::Off)))
+        ));
+
+        let actions = client
+            .get_actions()
+            .await
+            .expect("Quick Actions should be read from the fixtures");
+
+        assert!(
+            actions[0]
+                .activate()
+                .await
+                .expect("Activation should be recorded")
+        );
+
+        assert!(matches!(
+            client.recorded_calls().as_slice(),
+            [
+                RecordedCall::SetState { product_id, product_type, .. },
+                RecordedCall::Activate { action_id },
+            ] if product_id == "heating-1" && product_type == "heating" && action_id == "action-1"
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_no_requests_are_sent_to_hive() {
+        let client = client();
+
+        // Any request to Hive would fail (as the tokens are placeholders), so succeeding shows
+        // each call was served by the fixtures.
+        client.warm_up().await.expect("Warm up should be skipped");
+        client
+            .health_check()
+            .await
+            .expect("Health check should be served from the fixtures");
+
+        let body = serde_json::json!({ "target": 18.0 });
+        assert_eq!(
+            client
+                .post_raw("nodes/heating/heating-1", &body)
+                .await
+                .expect("Post should be recorded"),
+            Value::Null
+        );
+        assert!(matches!(
+            client.recorded_calls().as_slice(),
+            [RecordedCall::Post { path, body: posted }]
+                if path == "nodes/heating/heating-1" && *posted == body
+        ));
+
+        assert_eq!(
+            client
+                .get_raw("/nodes/heating")
+                .await
+                .expect("Raw response should be read from the fixtures")[0]["id"],
+            "heating-1"
+        );
+        assert!(matches!(
+            client.get_raw("nodes/hotwater").await,
+            Err(ApiError::FixtureUnavailable(_))
+        ));
+
+        let products = client
+            .get_products()
+            .await
+            .expect("Products should be read from the fixtures");
+        assert!(matches!(
+            products[0]
+                .get_usage(chrono::Utc::now() - chrono::Duration::days(1)..chrono::Utc::now())
+                .await,
+            Err(ApiError::FixtureUnavailable(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_rejected_atomic_changes_are_not_restored() {
+        let client = client();
+
+        let mut products = client
+            .get_products()
+            .await
+            .expect("Products should be read from the fixtures");
+
+        assert!(matches!(
+            products[0]
+                .set_states_atomic(&States(vec![State::TargetTemperature(40.0)]))
+                .await,
+            Err(ApiError::InvalidTargetTemperature(_))
+        ));
+        assert!(client.recorded_calls().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_missing_fixtures_are_reported() {
+        let client = Client::from_fixtures("does-not-exist");
+
+        assert!(matches!(
+            client.get_products().await,
+            Err(ApiError::FixtureUnavailable(_))
+        ));
+    }
+}
@@ -0,0 +1,40 @@
+use crate::client::api::error::ApiError;
+use crate::client::api::{HiveApi, ensure_success};
+use crate::client::authentication::Tokens;
+use crate::helper::url::{BEEKEEPER_BASE_URL, Url, get_base_url};
+
+impl HiveApi {
+    pub(crate) async fn health_check(&self, tokens: &Tokens) -> Result<(), ApiError> {
+        #[cfg(feature = "fixtures")]
+        if let Some(fixtures) = &self.fixtures {
+            return fixtures.load("devices").map(|_| ());
+        }
+
+        // The devices endpoint is one of the smallest authenticated endpoints, and the response
+        // body is never read, so only the status of the response is checked.
+        let response = self
+            .client
+            .get(get_base_url(&Url::Device))
+            .header("Authorization", &tokens.id_token)
+            .send()
+            .await?;
+
+        ensure_success(response)?;
+
+        Ok(())
+    }
+
+    pub(crate) async fn warm_up(&self) -> Result<(), ApiError> {
+        // There's no connection to establish when serving recorded responses.
+        #[cfg(feature = "fixtures")]
+        if self.fixtures.is_some() {
+            return Ok(());
+        }
+
+        // Any response at all means the connection (and TLS session) has been established and
+        // pooled for subsequent requests, so the status of the response doesn't matter.
+        self.client.head(BEEKEEPER_BASE_URL).send().await?;
+
+        Ok(())
+    }
+}
@@ -1,4 +1,6 @@
 mod error;
+mod health;
+mod raw;
 
 /// Support for [Quick Actions](https://www.hivehome.com/ie/support/Help_Using_Hive/HUH_General/What-are-Quick-Actions) API.
 pub mod actions;
@@ -9,20 +11,296 @@ pub mod devices;
 /// Support for Hive Products API (Heating, Hot Water, etc).
 pub mod products;
 
+/// Support for serving the Hive API from recorded responses, in place of a live Hive account.
+#[cfg(feature = "fixtures")]
+pub mod fixtures;
+
 /// Support for the Hive Weather API.
 pub mod weather;
 
 pub use error::ApiError;
 
+use crate::client::authentication::Tokens;
+use crate::helper::url::{Url, get_base_url};
+use reqwest::{RequestBuilder, Response, StatusCode};
+use serde::de::DeserializeOwned;
+use serde_json::Value;
+
+/// The default maximum size (in bytes) of a response body which will be read from the Hive API.
+pub const DEFAULT_MAX_RESPONSE_SIZE: usize = 4 * 1024 * 1024;
+
+/// The header a caller-supplied correlation ID is sent to the Hive API in.
+const CORRELATION_ID_HEADER: &str = "X-Correlation-Id";
+
+/// The header the Hive API identifies each request with, in its response.
+const REQUEST_ID_HEADER: &str = "x-amzn-requestid";
+
+/// The result of a request to the Hive API, along with the IDs which identify the request -
+/// for example, to tie an action in a caller's logs to the exact call made to Hive.
+#[derive(Debug, Clone)]
+#[non_exhaustive]
+pub struct Correlated<T> {
+    /// The result of the request.
+    pub value: T,
+
+    /// The correlation ID supplied by the caller, which was sent with the request.
+    pub correlation_id: Option<String>,
+
+    /// The ID Hive assigned to the request, if it was included in the response.
+    pub request_id: Option<String>,
+}
+
+/// Send a request to the Hive API, along with a caller-supplied correlation ID (if any).
+async fn send_correlated(
+    request: RequestBuilder,
+    correlation_id: Option<&str>,
+) -> Result<Correlated<Response>, ApiError> {
+    let request = match correlation_id {
+        Some(correlation_id) => request.header(CORRELATION_ID_HEADER, correlation_id),
+        None => request,
+    };
+
+    let response = request.send().await?;
+
+    Ok(Correlated {
+        request_id: response
+            .headers()
+            .get(REQUEST_ID_HEADER)
+            .and_then(|request_id| request_id.to_str().ok())
+            .map(ToString::to_string),
+        correlation_id: correlation_id.map(ToString::to_string),
+        value: response,
+    })
+}
+
+impl<T> Correlated<T> {
+    /// Apply a function to the result of the request, keeping the IDs which identify it.
+    pub(crate) fn try_map<U>(
+        self,
+        f: impl FnOnce(T) -> Result<U, ApiError>,
+    ) -> Result<Correlated<U>, ApiError> {
+        Ok(Correlated {
+            value: f(self.value)?,
+            correlation_id: self.correlation_id,
+            request_id: self.request_id,
+        })
+    }
+}
+
 #[derive(Debug)]
 pub struct HiveApi {
     client: reqwest::Client,
+    max_response_size: usize,
+    #[cfg(feature = "fixtures")]
+    fixtures: Option<fixtures::Fixtures>,
 }
 
 impl HiveApi {
     pub(crate) fn new() -> Self {
         Self {
             client: reqwest::Client::new(),
+            max_response_size: DEFAULT_MAX_RESPONSE_SIZE,
+            #[cfg(feature = "fixtures")]
+            fixtures: None,
         }
     }
+
+    /// Serve each request from recorded responses, in place of the Hive API.
+    #[cfg(feature = "fixtures")]
+    pub(crate) fn set_fixtures(&mut self, fixtures: fixtures::Fixtures) {
+        self.fixtures = Some(fixtures);
+    }
+
+    #[cfg(feature = "fixtures")]
+    pub(crate) const fn fixtures(&self) -> Option<&fixtures::Fixtures> {
+        self.fixtures.as_ref()
+    }
+
+    pub(crate) const fn set_max_response_size(&mut self, max_response_size: usize) {
+        self.max_response_size = max_response_size;
+    }
+
+    /// Decode the JSON body of a successful response from the Hive API.
+    ///
+    /// The status of the response is checked before the body is decoded, so that an error body
+    /// is never decoded as though it were the expected response, and the body is read no
+    /// further than the maximum response size.
+    async fn decode_json<T: DeserializeOwned>(&self, response: Response) -> Result<T, ApiError> {
+        let mut response = ensure_success(response)?;
+
+        if response
+            .content_length()
+            .is_some_and(|length| length > self.max_response_size as u64)
+        {
+            return Err(ApiError::ResponseTooLarge(self.max_response_size));
+        }
+
+        let mut body = Vec::new();
+
+        while let Some(chunk) = response.chunk().await? {
+            if body.len() + chunk.len() > self.max_response_size {
+                return Err(ApiError::ResponseTooLarge(self.max_response_size));
+            }
+
+            body.extend_from_slice(&chunk);
+        }
+
+        Ok(serde_json::from_slice(&body)?)
+    }
+
+    /// Decode the JSON body of a successful response from the Hive API which holds a list,
+    /// either as a bare array or wrapped in an object under one of the given keys (for
+    /// example, `{ "products": [...] }`) alongside other metadata.
+    async fn decode_list<T: DeserializeOwned>(
+        &self,
+        response: Response,
+        keys: &[&str],
+    ) -> Result<Vec<T>, ApiError> {
+        let body: Value = self.decode_json(response).await?;
+
+        Ok(serde_json::from_value(unwrap_list(body, keys))?)
+    }
+
+    /// Retrieve a list from the Hive API (or from the recorded fixture of the given name, if
+    /// the client is serving fixtures), wrapped under any of the given keys.
+    async fn fetch_list<T: DeserializeOwned>(
+        &self,
+        tokens: &Tokens,
+        url: &Url<'_>,
+        #[cfg_attr(not(feature = "fixtures"), allow(unused_variables))] fixture: &str,
+        keys: &[&str],
+    ) -> Result<Vec<T>, ApiError> {
+        #[cfg(feature = "fixtures")]
+        if let Some(fixtures) = &self.fixtures {
+            return Ok(serde_json::from_value(unwrap_list(
+                fixtures.load(fixture)?,
+                keys,
+            ))?);
+        }
+
+        let response = self
+            .client
+            .get(get_base_url(url))
+            .header("Authorization", &tokens.id_token)
+            .send()
+            .await;
+
+        self.decode_list(response?, keys).await
+    }
+}
+
+/// Take the list out of an object wrapping it under one of the given keys, or leave the body
+/// as-is if it is not wrapped.
+fn unwrap_list(body: Value, keys: &[&str]) -> Value {
+    match body {
+        Value::Object(mut object) => keys
+            .iter()
+            .find_map(|key| object.remove(*key))
+            .unwrap_or(Value::Object(object)),
+        body => body,
+    }
+}
+
+/// Reject a response which shows the tokens sent with the request were not accepted by the
+/// Hive API (for example, because they expired while the request was in-flight).
+fn ensure_authorized(response: Response) -> Result<Response, ApiError> {
+    match response.status() {
+        status @ (StatusCode::UNAUTHORIZED | StatusCode::FORBIDDEN) => {
+            Err(ApiError::Unauthorized(status))
+        }
+        _ => Ok(response),
+    }
+}
+
+/// Reject a response which was not successful, including those where the tokens sent with the
+/// request were not accepted by the Hive API.
+fn ensure_success(response: Response) -> Result<Response, ApiError> {
+    let response = ensure_authorized(response)?;
+
+    if !response.status().is_success() {
+        return Err(ApiError::UnexpectedStatus(response.status()));
+    }
+
+    Ok(response)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn response(body: &'static str) -> Response {
+        Response::from(http::Response::new(body))
+    }
+
+    #[tokio::test]
+    async fn test_correlation_ids_are_mapped_with_the_result() {
+        let correlated = Correlated {
+            value: response("[]"),
+            correlation_id: Some("correlation-id".to_string()),
+            request_id: Some("request-id".to_string()),
+        }
+        .try_map(|response| Ok(response.status() == StatusCode::OK))
+        .expect("Result should be mapped");
+
+        assert!(correlated.value);
+        assert_eq!(correlated.correlation_id.as_deref(), Some("correlation-id"));
+        assert_eq!(correlated.request_id.as_deref(), Some("request-id"));
+    }
+
+    #[tokio::test]
+    async fn test_response_bodies_are_limited_in_size() {
+        let mut api = HiveApi::new();
+        api.set_max_response_size(8);
+
+        assert_eq!(
+            api.decode_json::<Vec<u8>>(response("[1,2,3]"))
+                .await
+                .expect("Response should be decoded"),
+            [1, 2, 3]
+        );
+        assert!(matches!(
+            api.decode_json::<Vec<u8>>(response("[1,2,3,4,5]")).await,
+            Err(ApiError::ResponseTooLarge(8))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_unsuccessful_responses_are_not_decoded() {
+        let mut response = http::Response::new("[]");
+        *response.status_mut() = StatusCode::INTERNAL_SERVER_ERROR;
+
+        assert!(matches!(
+            HiveApi::new()
+                .decode_json::<Vec<u8>>(Response::from(response))
+                .await,
+            Err(ApiError::UnexpectedStatus(
+                StatusCode::INTERNAL_SERVER_ERROR
+            ))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_lists_are_decoded_bare_or_wrapped() {
+        for body in [
+            "[1,2,3]",
+            r#"{ "products": [1,2,3], "total": 3 }"#,
+            r#"{ "data": [1,2,3] }"#,
+        ] {
+            assert_eq!(
+                HiveApi::new()
+                    .decode_list::<u8>(response(body), &["products", "data"])
+                    .await
+                    .expect("List should be decoded"),
+                [1, 2, 3],
+                "{body}"
+            );
+        }
+
+        assert!(matches!(
+            HiveApi::new()
+                .decode_list::<u8>(response(r#"{ "devices": [1] }"#), &["products"])
+                .await,
+            Err(ApiError::InvalidResponse(_))
+        ));
+    }
 }
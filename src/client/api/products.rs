@@ -1,17 +1,51 @@
 use crate::Client;
 use crate::client::api::ApiError;
-use crate::client::api::HiveApi;
+use crate::client::api::{Correlated, HiveApi, ensure_authorized, send_correlated};
+#[cfg(feature = "fixtures")]
+use crate::client::api::{fixtures::RecordedCall, unwrap_list};
 use crate::client::authentication::Tokens;
+use crate::devices::Device;
+use crate::helper::target;
+use crate::helper::temperature::{fahrenheit_to_celsius, round_to_half_degree};
+use crate::helper::timestamp;
 use crate::helper::url::{Url, get_base_url};
-use chrono::{DateTime, Utc, serde::ts_milliseconds, serde::ts_milliseconds_option};
+use chrono::{DateTime, Datelike, Days, NaiveTime, TimeZone, Utc, Weekday};
+use futures_util::future::join_all;
 use reqwest::StatusCode;
-use serde::{Deserialize, Serialize};
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
 use serde_json::Value;
 use serde_with::{EnumMap, serde_as};
 use std::collections::HashMap;
 use std::fmt;
 use std::fmt::{Debug, Display, Formatter};
-use std::ops::Deref;
+use std::ops::{Deref, Range};
+use std::str::FromStr;
+use std::sync::LazyLock;
+use std::time::Duration;
+use tokio_util::sync::CancellationToken;
+
+/// The lowest target temperature (in Celsius) which Hive will accept for a Heating product.
+pub const MIN_TARGET_TEMPERATURE: f32 = 5.0;
+
+/// The highest target temperature (in Celsius) which Hive will accept for a Heating product.
+pub const MAX_TARGET_TEMPERATURE: f32 = 32.0;
+
+/// Reject a target temperature which is outside of the range supported by Hive.
+///
+/// Products may accept a narrower range, which is checked when states are set on them.
+pub(crate) fn ensure_supported_target(celsius: f32) -> Result<(), ApiError> {
+    if !(MIN_TARGET_TEMPERATURE..=MAX_TARGET_TEMPERATURE).contains(&celsius) {
+        return Err(ApiError::InvalidTargetTemperature(celsius));
+    }
+
+    Ok(())
+}
+
+/// Products are enabled unless the Hive API reports otherwise.
+const fn enabled() -> bool {
+    true
+}
 
 #[derive(Serialize, Deserialize, Debug)]
 #[non_exhaustive]
@@ -21,22 +55,266 @@ pub struct Properties {
     /// The ID of the zone the device is located in (if applicable).
     pub zone_id: Option<String>,
 
+    #[serde(default, rename = "zoneName")]
+    /// The name of the zone the device is located in (if applicable).
+    ///
+    /// This is not always included by the Hive API, see [`crate::Client::get_products_with_zones`]
+    /// to fill it in from the devices in the Hive account.
+    pub zone_name: Option<String>,
+
     #[serde(rename = "online")]
     /// Whether the device is currently online or not.
     pub is_online: bool,
 
+    #[serde(default = "enabled", rename = "enabled")]
+    /// Whether the product is enabled in the Hive account. Disabled products (for example,
+    /// those which have been decommissioned but not deleted) are hidden by the Hive app.
+    pub is_enabled: bool,
+
     #[serde(rename = "working")]
     /// Whether the device is currently running or not.
     pub is_working: bool,
 
+    #[serde(default, deserialize_with = "deserialize_optional_temperature")]
     /// The current temperature by the Hive product.
     pub temperature: Option<f32>,
 
+    #[serde(default, rename = "sensors", with = "sensor_temperatures")]
+    /// The individual temperature readings which make up [`Properties::temperature`], keyed by
+    /// the ID of the device reporting them - for example, the thermostat and any radiator
+    /// valves in a zone.
+    ///
+    /// This is empty if Hive only reported a single temperature.
+    pub sensor_temperatures: Vec<(String, f32)>,
+
+    #[serde(default, deserialize_with = "deserialize_humidity")]
+    /// The current relative humidity (as a percentage) reported by the Hive product, if it has
+    /// a humidity sensor.
+    pub humidity: Option<u8>,
+
+    #[serde(default, rename = "optimumStartTime", with = "timestamp::option")]
+    /// The date and time Hive started (or will start) heating early, so that the target
+    /// temperature of the next scheduled slot is reached by the time it starts.
+    ///
+    /// This is only reported by Heating products with [`State::OptimumStart`] enabled.
+    pub optimum_start_time: Option<DateTime<Utc>>,
+
+    #[serde(default)]
+    /// The states the Hive product will return to once a temporary override (such as a boost)
+    /// ends, if one is active.
+    pub previous: Option<States>,
+
+    #[serde(flatten)]
+    #[allow(missing_docs)]
+    pub extra: HashMap<String, Value>,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+#[non_exhaustive]
+/// A Hive Heating product.
+pub struct Heating {
+    /// The unique ID of the Hive Heating product.
+    pub id: String,
+
+    #[serde(default, with = "timestamp::option")]
+    /// The date and time when the Hive Heating product last communicated with the Hive servers.
+    pub last_seen: Option<DateTime<Utc>>,
+
+    #[serde(with = "timestamp")]
+    #[serde(rename = "created")]
+    /// The date and time when the Hive Heating product was first created.
+    pub created_at: DateTime<Utc>,
+
+    #[serde(rename = "props")]
+    /// The properties of the Hive Heating product.
+    pub properties: Properties,
+
+    /// The current state of the Hive Heating product.
+    pub state: States,
+
+    #[serde(flatten)]
+    #[allow(missing_docs)]
+    pub extra: HashMap<String, Value>,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+#[non_exhaustive]
+/// A Hive Hot Water product.
+pub struct HotWater {
+    /// The unique ID of the Hive Hot Water product.
+    pub id: String,
+
+    #[serde(default, with = "timestamp::option")]
+    /// The date and time when the Hive Hot Water product last communicated with the Hive servers.
+    pub last_seen: Option<DateTime<Utc>>,
+
+    #[serde(with = "timestamp")]
+    #[serde(rename = "created")]
+    /// The date and time when the Hive Hot Water product was first created.
+    pub created_at: DateTime<Utc>,
+
+    #[serde(rename = "props")]
+    /// The properties of the Hive Hot Water product.
+    pub properties: Properties,
+
+    /// The current state of the Hive Hot Water product.
+    pub state: States,
+
+    #[serde(flatten)]
+    #[allow(missing_docs)]
+    pub extra: HashMap<String, Value>,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+#[non_exhaustive]
+/// A Hive Leak Sensor product.
+///
+/// Leak Sensors are read-only, so their states cannot be changed.
+pub struct LeakSensor {
+    /// The unique ID of the Hive Leak Sensor product.
+    pub id: String,
+
+    #[serde(default, with = "timestamp::option")]
+    /// The date and time when the Hive Leak Sensor product last communicated with the Hive
+    /// servers.
+    pub last_seen: Option<DateTime<Utc>>,
+
+    #[serde(with = "timestamp")]
+    #[serde(rename = "created")]
+    /// The date and time when the Hive Leak Sensor product was first created.
+    pub created_at: DateTime<Utc>,
+
+    #[serde(rename = "props")]
+    /// The properties of the Hive Leak Sensor product.
+    pub properties: LeakSensorProperties,
+
+    #[serde(default)]
+    /// The current state of the Hive Leak Sensor product.
+    pub state: States,
+
     #[serde(flatten)]
     #[allow(missing_docs)]
     pub extra: HashMap<String, Value>,
 }
 
+#[derive(Serialize, Deserialize, Debug)]
+#[non_exhaustive]
+/// Properties of a Hive Leak Sensor product.
+pub struct LeakSensorProperties {
+    #[serde(rename = "online")]
+    /// Whether the device is currently online or not.
+    pub is_online: bool,
+
+    #[serde(default = "enabled", rename = "enabled")]
+    /// Whether the Hive Leak Sensor is enabled in the Hive account.
+    pub is_enabled: bool,
+
+    #[serde(default)]
+    /// Whether the Hive Leak Sensor is currently detecting a leak.
+    pub leak: bool,
+
+    #[serde(default, rename = "battery")]
+    /// The battery percentage of the Hive Leak Sensor, if reported.
+    pub battery_percentage: Option<u8>,
+}
+
+impl LeakSensor {
+    /// Whether the Hive Leak Sensor is currently detecting a leak.
+    #[must_use]
+    pub const fn leak_detected(&self) -> bool {
+        self.properties.leak
+    }
+
+    /// The battery percentage of the Hive Leak Sensor, if reported.
+    #[must_use]
+    pub const fn battery_percentage(&self) -> Option<u8> {
+        self.properties.battery_percentage
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+/// The type of a Hive product whose states can be set, used to act on a product by its ID
+/// without retrieving it first (see [`crate::Client::set_product_state_by_id`]).
+pub enum ProductType {
+    /// A [`ProductData::Heating`] product.
+    Heating,
+
+    /// A [`ProductData::HotWater`] product.
+    HotWater,
+}
+
+impl ProductType {
+    /// The type tag of the product, as used by the Hive API.
+    #[must_use]
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::Heating => "heating",
+            Self::HotWater => "hotwater",
+        }
+    }
+
+    /// Whether a state can be set on this type of product - for example,
+    /// [`State::TargetTemperature`] is not applicable to a [`ProductType::HotWater`] product.
+    #[must_use]
+    pub const fn accepts_state(self, state: &State) -> bool {
+        match self {
+            Self::Heating => true,
+            Self::HotWater => matches!(
+                state,
+                State::Mode(_)
+                    | State::Name(_)
+                    | State::Status(_)
+                    | State::Boost(_)
+                    | State::Schedule(_)
+            ),
+        }
+    }
+
+    /// Reject any of the states which are not applicable to this type of product.
+    pub(crate) fn ensure_applicable(self, states: &States) -> Result<(), ApiError> {
+        if let Some(state) = states.iter().find(|state| !self.accepts_state(state)) {
+            return Err(ApiError::InvalidStateForProduct {
+                state: Box::new(state.clone()),
+                product_type: self.to_string(),
+            });
+        }
+
+        Ok(())
+    }
+}
+
+impl Display for ProductType {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+impl FromStr for ProductType {
+    type Err = ApiError;
+
+    fn from_str(r#type: &str) -> Result<Self, Self::Err> {
+        [Self::Heating, Self::HotWater]
+            .into_iter()
+            .find(|product_type| product_type.as_str() == r#type)
+            .ok_or_else(|| ApiError::UnsupportedProduct(r#type.to_string()))
+    }
+}
+
+impl Serialize for ProductType {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(self.as_str())
+    }
+}
+
+impl<'de> Deserialize<'de> for ProductType {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        String::deserialize(deserializer)?
+            .parse()
+            .map_err(serde::de::Error::custom)
+    }
+}
+
 #[derive(Serialize, Deserialize, Debug)]
 #[serde(rename_all = "lowercase")]
 #[serde(tag = "type")]
@@ -44,68 +322,269 @@ pub struct Properties {
 /// Data about a Hive product.
 pub enum ProductData {
     /// A Hive Heating product.
-    Heating {
-        /// The unique ID of the Hive Heating product.
-        id: String,
+    Heating(Heating),
+
+    /// A Hive Hot Water product.
+    HotWater(HotWater),
 
-        #[serde(default, with = "ts_milliseconds_option")]
-        /// The date and time when the Hive Heating product last communicated with the Hive servers.
-        last_seen: Option<DateTime<Utc>>,
+    /// A Hive Leak Sensor product.
+    LeakSensor(LeakSensor),
+
+    #[serde(other)]
+    /// A product which is yet to be mapped by the crate.
+    Unknown,
+}
 
-        #[serde(with = "ts_milliseconds")]
-        #[serde(rename = "created")]
-        /// The date and time when the Hive Heating product was first created.
-        created_at: DateTime<Utc>,
+impl ProductData {
+    /// The type tags (as reported by the Hive API) of the products which are mapped by the
+    /// crate. Products of any other type are returned as [`ProductData::Unknown`].
+    pub const SUPPORTED_TYPES: &[&str] = &[
+        ProductType::Heating.as_str(),
+        ProductType::HotWater.as_str(),
+        "leaksensor",
+    ];
 
-        #[serde(rename = "props")]
-        /// The properties of the Hive Heating product.
-        properties: Properties,
+    /// The Heating product, if this is a [`ProductData::Heating`].
+    #[must_use]
+    pub const fn as_heating(&self) -> Option<&Heating> {
+        match self {
+            Self::Heating(heating) => Some(heating),
+            _ => None,
+        }
+    }
 
-        /// The current state of the Hive Heating product.
-        state: States,
+    /// The Hot Water product, if this is a [`ProductData::HotWater`].
+    #[must_use]
+    pub const fn as_hot_water(&self) -> Option<&HotWater> {
+        match self {
+            Self::HotWater(hot_water) => Some(hot_water),
+            _ => None,
+        }
+    }
 
-        #[serde(flatten)]
-        #[allow(missing_docs)]
-        extra: HashMap<String, Value>,
-    },
+    /// The Leak Sensor product, if this is a [`ProductData::LeakSensor`].
+    #[must_use]
+    pub const fn as_leak_sensor(&self) -> Option<&LeakSensor> {
+        match self {
+            Self::LeakSensor(leak_sensor) => Some(leak_sensor),
+            _ => None,
+        }
+    }
 
-    /// A Hive Hot Water product.
-    HotWater {
-        /// The unique ID of the Hive Hot Water product.
-        id: String,
+    /// The type of the product, as used in the paths of the Hive API.
+    pub(crate) const fn r#type(&self) -> &'static str {
+        match self {
+            Self::Heating(_) => ProductType::Heating.as_str(),
+            Self::HotWater(_) => ProductType::HotWater.as_str(),
+            Self::LeakSensor(_) => "leaksensor",
+            Self::Unknown => "unknown",
+        }
+    }
+
+    /// The type of the product, if its states can be set.
+    #[must_use]
+    pub const fn product_type(&self) -> Option<ProductType> {
+        match self {
+            Self::Heating(_) => Some(ProductType::Heating),
+            Self::HotWater(_) => Some(ProductType::HotWater),
+            Self::LeakSensor(_) | Self::Unknown => None,
+        }
+    }
 
-        #[serde(default, with = "ts_milliseconds_option")]
-        /// The date and time when the Hive Hot Water product last communicated with the Hive servers.
-        last_seen: Option<DateTime<Utc>>,
+    /// Whether the product is enabled in the Hive account.
+    ///
+    /// Disabled products are only returned by [`crate::Client::get_products`] when the client is
+    /// configured to [include disabled products](crate::Client::include_disabled_products).
+    #[must_use]
+    pub const fn is_enabled(&self) -> bool {
+        match self {
+            Self::Heating(Heating { properties, .. })
+            | Self::HotWater(HotWater { properties, .. }) => properties.is_enabled,
+            Self::LeakSensor(LeakSensor { properties, .. }) => properties.is_enabled,
+            Self::Unknown => true,
+        }
+    }
 
-        #[serde(with = "ts_milliseconds")]
-        #[serde(rename = "created")]
-        /// The date and time when the Hive Hot Water product was first created.
-        created_at: DateTime<Utc>,
+    /// Whether a state can be set on this type of product - for example,
+    /// [`State::TargetTemperature`] is not applicable to a [`ProductData::HotWater`] product.
+    ///
+    /// The states of [`ProductData::Unknown`] products are not known, so any state is accepted.
+    #[must_use]
+    pub const fn accepts_state(&self, state: &State) -> bool {
+        match self {
+            Self::Heating(_) => ProductType::Heating.accepts_state(state),
+            Self::HotWater(_) => ProductType::HotWater.accepts_state(state),
+            Self::LeakSensor(_) => false,
+            Self::Unknown => true,
+        }
+    }
 
-        #[serde(rename = "props")]
-        /// The properties of the Hive Hot Water product.
-        properties: Properties,
+    /// Decode a product returned by the Hive API, rejecting any products which are yet to be
+    /// mapped by the crate.
+    pub(crate) fn decode_known(raw: Value) -> Result<Self, ApiError> {
+        match Self::deserialize(&raw)? {
+            Self::Unknown => Err(ApiError::UnknownProductType { raw }),
+            data => Ok(data),
+        }
+    }
+}
 
-        /// The current state of the Hive Hot Water product.
-        state: States,
+impl Heating {
+    /// The lowest and highest target temperatures (in Celsius) the Heating product accepts,
+    /// where it reports its own bounds.
+    ///
+    /// Where these aren't reported, the product accepts targets between
+    /// [`MIN_TARGET_TEMPERATURE`] and [`MAX_TARGET_TEMPERATURE`].
+    #[must_use]
+    #[allow(clippy::cast_possible_truncation)]
+    pub fn target_temperature_bounds(&self) -> Option<(f32, f32)> {
+        let bound = |key: &str| {
+            self.properties
+                .extra
+                .get(key)
+                .and_then(Value::as_f64)
+                .map(|bound| bound as f32)
+        };
 
-        #[serde(flatten)]
-        #[allow(missing_docs)]
-        extra: HashMap<String, Value>,
-    },
+        match (bound("minHeat")?, bound("maxHeat")?) {
+            (min, max) if min <= max => Some((min, max)),
+            _ => None,
+        }
+    }
 
-    #[serde(other)]
-    /// A product which is yet to be mapped by the crate.
-    Unknown,
+    /// Whether the Heating product is currently governed by the account's Holiday Mode, in
+    /// which case it holds a fixed (usually low) target temperature until the holiday ends.
+    ///
+    /// This is read from the flag Hive reports alongside each Heating product, and is `false`
+    /// if the flag is not reported.
+    #[must_use]
+    pub fn is_in_holiday_mode(&self) -> bool {
+        match self.properties.extra.get("holidayMode") {
+            Some(Value::Bool(enabled)) => *enabled,
+            Some(Value::Object(holiday_mode)) => holiday_mode
+                .get("enabled")
+                .and_then(Value::as_bool)
+                .unwrap_or(false),
+            _ => false,
+        }
+    }
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+impl HotWater {
+    /// The current temperature (in Celsius) of the water in the cylinder, where the Hot Water
+    /// product has a cylinder sensor.
+    ///
+    /// This is `None` for Hot Water products which only report whether they are on or off.
+    #[must_use]
+    #[allow(clippy::cast_possible_truncation)]
+    pub fn water_temperature(&self) -> Option<f32> {
+        self.properties.temperature.or_else(|| {
+            ["waterTemperature", "cylinderTemperature"]
+                .iter()
+                .find_map(|key| self.properties.extra.get(*key)?.as_f64())
+                .map(|temperature| temperature as f32)
+        })
+    }
+}
+
+impl TryFrom<ProductData> for Heating {
+    type Error = ApiError;
+
+    fn try_from(data: ProductData) -> Result<Self, Self::Error> {
+        match data {
+            ProductData::Heating(heating) => Ok(heating),
+            data => Err(ApiError::UnsupportedProduct(data.r#type().to_string())),
+        }
+    }
+}
+
+impl TryFrom<ProductData> for HotWater {
+    type Error = ApiError;
+
+    fn try_from(data: ProductData) -> Result<Self, Self::Error> {
+        match data {
+            ProductData::HotWater(hot_water) => Ok(hot_water),
+            data => Err(ApiError::UnsupportedProduct(data.r#type().to_string())),
+        }
+    }
+}
+
+impl TryFrom<ProductData> for LeakSensor {
+    type Error = ApiError;
+
+    fn try_from(data: ProductData) -> Result<Self, Self::Error> {
+        match data {
+            ProductData::LeakSensor(leak_sensor) => Ok(leak_sensor),
+            data => Err(ApiError::UnsupportedProduct(data.r#type().to_string())),
+        }
+    }
+}
+
+impl Display for ProductData {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let (kind, id, properties, state) = match self {
+            Self::Heating(Heating {
+                id,
+                properties,
+                state,
+                ..
+            }) => ("Heating", id, properties, state),
+            Self::HotWater(HotWater {
+                id,
+                properties,
+                state,
+                ..
+            }) => ("Hot Water", id, properties, state),
+            Self::LeakSensor(LeakSensor {
+                id,
+                properties,
+                state,
+                ..
+            }) => {
+                write!(f, "Leak Sensor")?;
+
+                if let Some(name) = state.name() {
+                    write!(f, " \"{name}\"")?;
+                }
+
+                let leak = if properties.leak {
+                    "leak detected"
+                } else {
+                    "no leak"
+                };
+
+                return write!(f, " (id={id}, {leak})");
+            }
+            Self::Unknown => return write!(f, "Unknown product"),
+        };
+
+        write!(f, "{kind}")?;
+
+        if let Some(name) = state.name() {
+            write!(f, " \"{name}\"")?;
+        }
+
+        write!(f, " (id={id}")?;
+
+        if let Some(temperature) = properties.temperature {
+            write!(f, ", {temperature}°C")?;
+        }
+
+        if let Some(mode) = state.mode() {
+            write!(f, ", {mode}")?;
+        }
+
+        write!(f, ")")
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "UPPERCASE")]
+#[non_exhaustive]
 /// The mode of a Hive product.
 ///
 /// This applies to both [`ProductData::Heating`] and [`ProductData::HotWater`], which can be
-/// either in `Off`, `Schedule` or `Manual` mode.
+/// either in `Off`, `Schedule`, `Manual` or `Boost` mode.
 pub enum Mode {
     /// The product is turned off.
     Off,
@@ -115,6 +594,9 @@ pub enum Mode {
 
     /// The product is in manual mode.
     Manual,
+
+    /// The product is temporarily boosted, for the number of minutes in [`State::Boost`].
+    Boost,
 }
 
 impl Display for Mode {
@@ -123,11 +605,65 @@ impl Display for Mode {
             Self::Off => write!(f, "Off"),
             Self::Schedule => write!(f, "Schedule"),
             Self::Manual => write!(f, "Manual"),
+            Self::Boost => write!(f, "Boost"),
         }
     }
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(from = "String", into = "String")]
+/// The status of a Hive product, as reported in [`State::Status`].
+///
+/// For example, whether the boiler is currently being called for heat by a Heating product.
+pub enum HeatingStatus {
+    /// The product is currently heating.
+    On,
+
+    /// The product is not currently heating.
+    Off,
+
+    /// The product is heating ahead of a scheduled change, so that the target temperature is
+    /// reached by the time the change starts.
+    Preheat,
+
+    /// A status which is yet to be mapped by the crate.
+    Unknown(String),
+}
+
+impl From<String> for HeatingStatus {
+    fn from(status: String) -> Self {
+        match status.as_str() {
+            "ON" => Self::On,
+            "OFF" => Self::Off,
+            "PREHEAT" => Self::Preheat,
+            _ => Self::Unknown(status),
+        }
+    }
+}
+
+impl From<HeatingStatus> for String {
+    fn from(status: HeatingStatus) -> Self {
+        match status {
+            HeatingStatus::On => "ON".to_string(),
+            HeatingStatus::Off => "OFF".to_string(),
+            HeatingStatus::Preheat => "PREHEAT".to_string(),
+            HeatingStatus::Unknown(status) => status,
+        }
+    }
+}
+
+impl Display for HeatingStatus {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::On => write!(f, "On"),
+            Self::Off => write!(f, "Off"),
+            Self::Preheat => write!(f, "Preheat"),
+            Self::Unknown(status) => write!(f, "{status}"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 #[non_exhaustive]
 /// The state of a particular facet of a Hive product.
@@ -137,7 +673,7 @@ impl Display for Mode {
 pub enum State {
     #[serde(rename = "target")]
     /// The target temperature of the Hive product.
-    TargetTemperature(f32),
+    TargetTemperature(#[serde(deserialize_with = "deserialize_temperature")] f32),
 
     /// The mode of the Hive product.
     Mode(Mode),
@@ -146,14 +682,23 @@ pub enum State {
     Name(String),
 
     /// The status of the Hive product.
-    Status(String),
+    Status(HeatingStatus),
 
-    /// Whether the Hive product is currently boosted or not.
-    Boost(Option<bool>),
+    /// The boost of the Hive product, or `None` if the Hive product is not currently boosted.
+    Boost(Option<Boost>),
 
     /// The temperature of the Frost Protection mode.
+    ///
+    /// Whether Frost Protection is active at all is reported separately, by
+    /// [`State::FrostProtectionEnabled`].
     FrostProtection(u32),
 
+    /// Whether the Frost Protection mode is enabled or not.
+    ///
+    /// On firmware which supports it, this allows Frost Protection to be turned off entirely,
+    /// without changing the temperature set in [`State::FrostProtection`].
+    FrostProtectionEnabled(bool),
+
     /// Whether the Hive product will choose an Optimum Start time or not when
     /// in scheduled mode.
     OptimumStart(bool),
@@ -165,46 +710,789 @@ pub enum State {
     AutoBoostTarget(u32),
 
     /// The schedule for the Hive product, when it is in [`Mode::Schedule`].
-    Schedule(HashMap<String, Value>),
+    Schedule(Schedule),
 }
 
-impl Display for State {
-    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
-        match self {
-            Self::TargetTemperature(temp) => write!(f, "{temp}"),
-            Self::Mode(value) => write!(f, "{value}"),
-            Self::Name(value) | Self::Status(value) | Self::AutoBoost(value) => {
-                write!(f, "{value}")
-            }
-            Self::Boost(value) => write!(f, "{value:?}"),
-            Self::FrostProtection(value) | Self::AutoBoostTarget(value) => write!(f, "{value}"),
-            Self::OptimumStart(value) => write!(f, "{value}"),
-            Self::Schedule(value) => write!(f, "{value:?}"),
-        }
-    }
-}
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+/// A length of time which Hive will accept for a boost.
+pub enum BoostDuration {
+    /// Boost for 30 minutes.
+    HalfHour,
 
-#[serde_as]
-#[derive(Debug, Serialize, Deserialize)]
-/// A collection of states for a Hive product.
-pub struct States(#[serde_as(as = "EnumMap")] pub Vec<State>);
+    /// Boost for 1 hour.
+    OneHour,
 
-impl Deref for States {
-    type Target = Vec<State>;
+    /// Boost for 2 hours.
+    TwoHours,
 
-    fn deref(&self) -> &Self::Target {
-        &self.0
-    }
-}
+    /// Boost for 3 hours.
+    ThreeHours,
 
-/// A Product which is enabled in a Hive account.
-///
-/// For example, a [`ProductData::Heating`], a [`ProductData::HotWater`], etc.
-pub struct Product<'a> {
-    client: &'a Client,
+    /// Boost for 4 hours.
+    FourHours,
 
-    #[allow(missing_docs)]
-    pub data: ProductData,
+    /// Boost for 5 hours.
+    FiveHours,
+
+    /// Boost for 6 hours.
+    SixHours,
+}
+
+impl BoostDuration {
+    /// All of the boost durations accepted by Hive, from shortest to longest.
+    pub const ALL: [Self; 7] = [
+        Self::HalfHour,
+        Self::OneHour,
+        Self::TwoHours,
+        Self::ThreeHours,
+        Self::FourHours,
+        Self::FiveHours,
+        Self::SixHours,
+    ];
+
+    /// The length of the boost, in minutes.
+    #[must_use]
+    pub const fn minutes(self) -> u32 {
+        match self {
+            Self::HalfHour => 30,
+            Self::OneHour => 60,
+            Self::TwoHours => 120,
+            Self::ThreeHours => 180,
+            Self::FourHours => 240,
+            Self::FiveHours => 300,
+            Self::SixHours => 360,
+        }
+    }
+
+    /// The boost duration closest to a given length of time.
+    ///
+    /// ```rust
+    /// use hive_client::products::BoostDuration;
+    /// use std::time::Duration;
+    ///
+    /// assert_eq!(BoostDuration::nearest(Duration::from_secs(50 * 60)), BoostDuration::OneHour);
+    /// assert_eq!(BoostDuration::nearest(Duration::from_secs(10 * 60 * 60)), BoostDuration::SixHours);
+    /// ```
+    #[must_use]
+    pub fn nearest(duration: Duration) -> Self {
+        let minutes = duration.as_secs() / 60;
+
+        Self::ALL
+            .into_iter()
+            .min_by_key(|boost| u64::from(boost.minutes()).abs_diff(minutes))
+            .unwrap_or(Self::OneHour)
+    }
+
+    /// The shortest boost duration which lasts at least a given length of time, or `None` if
+    /// it's longer than the longest boost Hive will accept.
+    ///
+    /// ```rust
+    /// use hive_client::products::BoostDuration;
+    /// use std::time::Duration;
+    ///
+    /// assert_eq!(BoostDuration::at_least(Duration::from_secs(61 * 60)), Some(BoostDuration::TwoHours));
+    /// assert_eq!(BoostDuration::at_least(Duration::from_secs(7 * 60 * 60)), None);
+    /// ```
+    #[must_use]
+    pub fn at_least(duration: Duration) -> Option<Self> {
+        Self::ALL
+            .into_iter()
+            .find(|boost| Duration::from(*boost) >= duration)
+    }
+}
+
+impl From<BoostDuration> for Duration {
+    fn from(boost: BoostDuration) -> Self {
+        Self::from_secs(u64::from(boost.minutes()) * 60)
+    }
+}
+
+impl TryFrom<Duration> for BoostDuration {
+    type Error = ApiError;
+
+    /// Convert a length of time into a boost duration, if it's exactly one of the boost
+    /// durations accepted by Hive.
+    fn try_from(duration: Duration) -> Result<Self, Self::Error> {
+        Self::ALL
+            .into_iter()
+            .find(|boost| Duration::from(*boost) == duration)
+            .ok_or(ApiError::InvalidBoostDuration(duration))
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(untagged)]
+#[non_exhaustive]
+/// The boost of a Hive product ([`State::Boost`]), which Hive reports either as the number of
+/// minutes the product is boosted for, or as a flag.
+pub enum Boost {
+    /// The product is boosted for the given number of minutes.
+    Minutes(u32),
+
+    /// Whether the product is boosted, where Hive reports a flag in place of the minutes.
+    Active(bool),
+}
+
+impl Boost {
+    /// The number of minutes the product is boosted for, if Hive reported them.
+    #[must_use]
+    pub const fn minutes(self) -> Option<u32> {
+        match self {
+            Self::Minutes(minutes) => Some(minutes),
+            Self::Active(_) => None,
+        }
+    }
+
+    /// Whether the product is boosted.
+    #[must_use]
+    pub const fn is_active(self) -> bool {
+        match self {
+            Self::Minutes(_) => true,
+            Self::Active(active) => active,
+        }
+    }
+}
+
+/// A temperature reported by Hive, which some firmware reports as a numeric string rather than
+/// a number.
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum NumberOrString {
+    Number(f32),
+    String(String),
+}
+
+impl NumberOrString {
+    fn parse<E: serde::de::Error>(self) -> Result<f32, E> {
+        match self {
+            Self::Number(number) => Ok(number),
+            Self::String(string) => string.trim().parse().map_err(|_| {
+                E::invalid_value(
+                    serde::de::Unexpected::Str(&string),
+                    &"a numeric temperature",
+                )
+            }),
+        }
+    }
+}
+
+/// Deserialize a temperature, where Hive may report it as either a number or a numeric string.
+fn deserialize_temperature<'de, D>(deserializer: D) -> Result<f32, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    NumberOrString::deserialize(deserializer)?.parse()
+}
+
+/// Deserialize an optional temperature, where Hive may report it as either a number or a
+/// numeric string.
+fn deserialize_optional_temperature<'de, D>(deserializer: D) -> Result<Option<f32>, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    Option::<NumberOrString>::deserialize(deserializer)?
+        .map(NumberOrString::parse)
+        .transpose()
+}
+
+/// (De)serialize the individual temperature readings of a product, which Hive reports as a
+/// list of the devices reporting them.
+mod sensor_temperatures {
+    use super::NumberOrString;
+    use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+    #[derive(Serialize)]
+    struct Sensor<'a> {
+        id: &'a str,
+        temperature: f32,
+    }
+
+    #[derive(Deserialize)]
+    struct RawSensor {
+        id: String,
+        temperature: Option<NumberOrString>,
+    }
+
+    pub fn serialize<S: Serializer>(
+        sensors: &[(String, f32)],
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        serializer.collect_seq(sensors.iter().map(|(id, temperature)| Sensor {
+            id,
+            temperature: *temperature,
+        }))
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<Vec<(String, f32)>, D::Error> {
+        Option::<Vec<RawSensor>>::deserialize(deserializer)?
+            .unwrap_or_default()
+            .into_iter()
+            // Sensors which aren't currently reporting a temperature are left out.
+            .filter_map(|sensor| Some((sensor.id, sensor.temperature?)))
+            .map(|(id, temperature)| Ok((id, temperature.parse()?)))
+            .collect()
+    }
+}
+
+/// Deserialize a relative humidity percentage, where some Hive products report it with a
+/// fractional part.
+fn deserialize_humidity<'de, D>(deserializer: D) -> Result<Option<u8>, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    Ok(Option::<f64>::deserialize(deserializer)?
+        .filter(|humidity| humidity.is_finite())
+        .map(|humidity| humidity.round().clamp(0.0, 100.0) as u8))
+}
+
+impl State {
+    /// The kind of the state, as named by the Hive API - for example, `"target"` for
+    /// [`State::TargetTemperature`].
+    ///
+    /// This allows callers to branch on the kind of a state without an exhaustive match, which
+    /// would break as new states are supported.
+    #[must_use]
+    pub const fn kind(&self) -> &'static str {
+        match self {
+            Self::TargetTemperature(_) => "target",
+            Self::Mode(_) => "mode",
+            Self::Name(_) => "name",
+            Self::Status(_) => "status",
+            Self::Boost(_) => "boost",
+            Self::FrostProtection(_) => "frostProtection",
+            Self::FrostProtectionEnabled(_) => "frostProtectionEnabled",
+            Self::OptimumStart(_) => "optimumStart",
+            Self::AutoBoost(_) => "autoBoost",
+            Self::AutoBoostTarget(_) => "autoBoostTarget",
+            Self::Schedule(_) => "schedule",
+        }
+    }
+
+    /// Whether the state is of the same kind as another state, regardless of their values.
+    ///
+    /// For example, `State::TargetTemperature(18.0)` matches the kind of
+    /// `State::TargetTemperature(21.0)`.
+    #[must_use]
+    pub fn matches_kind(&self, other: &Self) -> bool {
+        std::mem::discriminant(self) == std::mem::discriminant(other)
+    }
+}
+
+#[derive(Debug, Default)]
+/// A builder for [`States`], created with [`States::builder`].
+///
+/// Setting the same state more than once replaces the earlier value.
+pub struct StatesBuilder {
+    product_type: Option<ProductType>,
+    states: Vec<State>,
+}
+
+impl StatesBuilder {
+    fn with(mut self, state: State) -> Self {
+        self.states
+            .retain(|existing| !existing.matches_kind(&state));
+        self.states.push(state);
+
+        self
+    }
+
+    /// Only allow states which are applicable to the given type of product (for example,
+    /// rejecting a target temperature for a [`ProductType::HotWater`] product).
+    #[must_use]
+    pub const fn for_product(mut self, product_type: ProductType) -> Self {
+        self.product_type = Some(product_type);
+
+        self
+    }
+
+    /// Set the name of the product ([`State::Name`]).
+    #[must_use]
+    pub fn name(self, name: &str) -> Self {
+        self.with(State::Name(name.to_string()))
+    }
+
+    /// Set the mode of the product ([`State::Mode`]).
+    #[must_use]
+    pub fn mode(self, mode: Mode) -> Self {
+        self.with(State::Mode(mode))
+    }
+
+    /// Set the target temperature of the product, in Celsius ([`State::TargetTemperature`]).
+    #[must_use]
+    pub fn target_temperature(self, celsius: f32) -> Self {
+        self.with(State::TargetTemperature(celsius))
+    }
+
+    /// Boost the product for a given length of time ([`State::Boost`]).
+    ///
+    /// Unless another mode is set, this also switches the product to [`Mode::Boost`].
+    #[must_use]
+    pub fn boost(self, duration: BoostDuration) -> Self {
+        self.with(State::Boost(Some(Boost::Minutes(duration.minutes()))))
+    }
+
+    /// Set the status of the product ([`State::Status`]) - for example, turning a Hot Water
+    /// product on.
+    #[must_use]
+    pub fn status(self, status: HeatingStatus) -> Self {
+        self.with(State::Status(status))
+    }
+
+    /// Set the temperature of the Frost Protection mode ([`State::FrostProtection`]).
+    #[must_use]
+    pub fn frost_protection(self, celsius: u32) -> Self {
+        self.with(State::FrostProtection(celsius))
+    }
+
+    /// Set whether the product will choose an Optimum Start time ([`State::OptimumStart`]).
+    #[must_use]
+    pub fn optimum_start(self, enabled: bool) -> Self {
+        self.with(State::OptimumStart(enabled))
+    }
+
+    /// Set the schedule of the product ([`State::Schedule`]).
+    #[must_use]
+    pub fn schedule(self, schedule: Schedule) -> Self {
+        self.with(State::Schedule(schedule))
+    }
+
+    /// Build the states, validating them together.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if:
+    /// 1. The target temperature is outside of the range supported by Hive
+    ///    ([`ApiError::InvalidTargetTemperature`]).
+    /// 2. A boost is set along with a mode other than [`Mode::Boost`]
+    ///    ([`ApiError::ConflictingStates`]).
+    /// 3. A state is not applicable to the [type of product](StatesBuilder::for_product) the
+    ///    states are for ([`ApiError::InvalidStateForProduct`]).
+    pub fn build(self) -> Result<States, ApiError> {
+        let mut states = States(self.states);
+
+        if let Some(celsius) = states.target_temperature() {
+            ensure_supported_target(celsius)?;
+        }
+
+        if states.boost().is_some() {
+            match states.mode() {
+                None => states.0.insert(0, State::Mode(Mode::Boost)),
+                Some(Mode::Boost) => {}
+                Some(mode) => {
+                    return Err(ApiError::ConflictingStates(format!(
+                        "a boost can't be set along with the {mode} mode"
+                    )));
+                }
+            }
+        }
+
+        if let Some(product_type) = self.product_type {
+            product_type.ensure_applicable(&states)?;
+        }
+
+        Ok(states)
+    }
+}
+
+impl Display for State {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::TargetTemperature(temp) => write!(f, "{temp}"),
+            Self::Mode(value) => write!(f, "{value}"),
+            Self::Name(value) | Self::AutoBoost(value) => write!(f, "{value}"),
+            Self::Status(value) => write!(f, "{value}"),
+            Self::Boost(value) => write!(f, "{value:?}"),
+            Self::FrostProtection(value) | Self::AutoBoostTarget(value) => write!(f, "{value}"),
+            Self::OptimumStart(value) | Self::FrostProtectionEnabled(value) => {
+                write!(f, "{value}")
+            }
+            Self::Schedule(value) => write!(f, "{value:?}"),
+        }
+    }
+}
+
+#[serde_as]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+/// A collection of states for a Hive product.
+pub struct States(#[serde_as(as = "EnumMap")] pub Vec<State>);
+
+impl Deref for States {
+    type Target = Vec<State>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl<'a> IntoIterator for &'a States {
+    type Item = &'a State;
+    type IntoIter = std::slice::Iter<'a, State>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.0.iter()
+    }
+}
+
+impl States {
+    /// Create a builder for a set of states, which validates the states together before
+    /// they are sent to Hive.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use hive_client::products::{Mode, States};
+    ///
+    /// let states = States::builder()
+    ///     .mode(Mode::Manual)
+    ///     .target_temperature(20.0)
+    ///     .build()
+    ///     .expect("States should be valid");
+    ///
+    /// assert_eq!(states.len(), 2);
+    /// ```
+    #[must_use]
+    pub fn builder() -> StatesBuilder {
+        StatesBuilder::default()
+    }
+
+    /// The states which switch a Heating product to [`Mode::Manual`] with a given target
+    /// temperature (in Celsius), to be sent to Hive in a single request.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the target temperature is outside of the range supported by Hive.
+    pub fn manual_target(celsius: f32) -> Result<Self, ApiError> {
+        ensure_supported_target(celsius)?;
+
+        Ok(Self(vec![
+            State::Mode(Mode::Manual),
+            State::TargetTemperature(celsius),
+        ]))
+    }
+
+    /// The target temperature of the Hive product ([`State::TargetTemperature`]).
+    #[must_use]
+    pub fn target_temperature(&self) -> Option<f32> {
+        self.iter().find_map(|state| match state {
+            State::TargetTemperature(target) => Some(*target),
+            _ => None,
+        })
+    }
+
+    /// The mode of the Hive product ([`State::Mode`]).
+    #[must_use]
+    pub fn mode(&self) -> Option<&Mode> {
+        self.iter().find_map(|state| match state {
+            State::Mode(mode) => Some(mode),
+            _ => None,
+        })
+    }
+
+    /// The name of the Hive product ([`State::Name`]).
+    #[must_use]
+    pub fn name(&self) -> Option<&str> {
+        self.iter().find_map(|state| match state {
+            State::Name(name) => Some(name.as_str()),
+            _ => None,
+        })
+    }
+
+    /// The status of the Hive product ([`State::Status`]).
+    #[must_use]
+    pub fn status(&self) -> Option<&HeatingStatus> {
+        self.iter().find_map(|state| match state {
+            State::Status(status) => Some(status),
+            _ => None,
+        })
+    }
+
+    /// The boost of the Hive product ([`State::Boost`]).
+    ///
+    /// Returns `None` if Hive did not report a boost for the Hive product. Hive may also report
+    /// a product which is not boosted with an inactive [`Boost::Active`] flag, so see
+    /// [`States::is_boosted`] to check whether the product is boosted.
+    #[must_use]
+    pub fn boost(&self) -> Option<Boost> {
+        self.iter().find_map(|state| match state {
+            State::Boost(boost) => *boost,
+            _ => None,
+        })
+    }
+
+    /// Whether the Hive product is currently boosted ([`State::Boost`]).
+    #[must_use]
+    pub fn is_boosted(&self) -> bool {
+        self.boost().is_some_and(Boost::is_active)
+    }
+
+    /// The temperature of the Frost Protection mode ([`State::FrostProtection`]).
+    #[must_use]
+    pub fn frost_protection(&self) -> Option<u32> {
+        self.iter().find_map(|state| match state {
+            State::FrostProtection(temperature) => Some(*temperature),
+            _ => None,
+        })
+    }
+
+    /// Whether the Hive product will choose an Optimum Start time ([`State::OptimumStart`]).
+    #[must_use]
+    pub fn optimum_start(&self) -> Option<bool> {
+        self.iter().find_map(|state| match state {
+            State::OptimumStart(enabled) => Some(*enabled),
+            _ => None,
+        })
+    }
+
+    /// The schedule for the Hive product ([`State::Schedule`]).
+    #[must_use]
+    pub fn schedule(&self) -> Option<&Schedule> {
+        self.iter().find_map(|state| match state {
+            State::Schedule(schedule) => Some(schedule),
+            _ => None,
+        })
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+/// A slot in a [`Schedule`], during which a set of states are applied to the Hive product.
+pub struct ScheduleSlot {
+    /// The number of minutes after midnight that the slot starts at.
+    pub start: u32,
+
+    /// The states applied to the Hive product while the slot is active.
+    ///
+    /// For example, the [`State::TargetTemperature`] of a Heating product.
+    pub value: States,
+}
+
+impl ScheduleSlot {
+    /// The time of day that the slot starts at.
+    #[must_use]
+    pub const fn starts_at(&self) -> Option<NaiveTime> {
+        NaiveTime::from_num_seconds_from_midnight_opt(self.start.saturating_mul(60), 0)
+    }
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+#[allow(missing_docs)]
+/// The weekly schedule of a Hive product, when it is in [`Mode::Schedule`].
+///
+/// Each day of the week is made up of a series of [`ScheduleSlot`]s, each of which
+/// runs until the next slot starts.
+pub struct Schedule {
+    #[serde(default)]
+    pub monday: Vec<ScheduleSlot>,
+
+    #[serde(default)]
+    pub tuesday: Vec<ScheduleSlot>,
+
+    #[serde(default)]
+    pub wednesday: Vec<ScheduleSlot>,
+
+    #[serde(default)]
+    pub thursday: Vec<ScheduleSlot>,
+
+    #[serde(default)]
+    pub friday: Vec<ScheduleSlot>,
+
+    #[serde(default)]
+    pub saturday: Vec<ScheduleSlot>,
+
+    #[serde(default)]
+    pub sunday: Vec<ScheduleSlot>,
+}
+
+/// The next change in a [`Schedule`], as found by [`Schedule::next_change`].
+#[derive(Debug)]
+pub struct ScheduleChange<'a, Tz: TimeZone> {
+    /// The date and time that the slot will start at.
+    pub at: DateTime<Tz>,
+
+    /// The slot which will start.
+    pub slot: &'a ScheduleSlot,
+}
+
+impl Schedule {
+    /// The slots scheduled on a given day of the week.
+    #[must_use]
+    pub fn slots(&self, weekday: Weekday) -> &[ScheduleSlot] {
+        match weekday {
+            Weekday::Mon => &self.monday,
+            Weekday::Tue => &self.tuesday,
+            Weekday::Wed => &self.wednesday,
+            Weekday::Thu => &self.thursday,
+            Weekday::Fri => &self.friday,
+            Weekday::Sat => &self.saturday,
+            Weekday::Sun => &self.sunday,
+        }
+    }
+
+    /// Find the next slot to start in the schedule, strictly after the given date and time.
+    ///
+    /// The slots in the schedule are times of day local to the Hive account, so they are
+    /// interpreted in the time zone of `from`. The search wraps across midnight and the end of
+    /// the week, and returns `None` only if the schedule has no slots at all.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use chrono::{TimeZone, Utc};
+    /// use hive_client::products::Schedule;
+    ///
+    /// let schedule: Schedule = serde_json::from_str(r#"{
+    ///     "monday": [
+    ///         { "start": 390, "value": { "target": 20.0 } },
+    ///         { "start": 1350, "value": { "target": 16.0 } }
+    ///     ]
+    /// }"#).expect("Schedule should be valid");
+    ///
+    /// // A Monday afternoon
+    /// let from = Utc.with_ymd_and_hms(2025, 6, 2, 15, 0, 0).unwrap();
+    ///
+    /// let change = schedule.next_change(&from).expect("A change should be scheduled");
+    ///
+    /// // The heating drops to 16°C at 22:30
+    /// assert_eq!(change.at, Utc.with_ymd_and_hms(2025, 6, 2, 22, 30, 0).unwrap());
+    /// ```
+    #[must_use]
+    pub fn next_change<Tz: TimeZone>(&self, from: &DateTime<Tz>) -> Option<ScheduleChange<'_, Tz>> {
+        let timezone = from.timezone();
+        let today = from.date_naive();
+
+        // Looking a full 7 days ahead covers slots earlier in the day than `from`, on the same
+        // day of the following week.
+        (0..=7).find_map(|offset| {
+            let date = today.checked_add_days(Days::new(offset))?;
+
+            let mut slots = self.slots(date.weekday()).iter().collect::<Vec<_>>();
+            slots.sort_by_key(|slot| slot.start);
+
+            slots.into_iter().find_map(|slot| {
+                let at = timezone
+                    .from_local_datetime(&date.and_time(slot.starts_at()?))
+                    .earliest()?;
+
+                (at > *from).then_some(ScheduleChange { at, slot })
+            })
+        })
+    }
+
+    /// Find the slot in the schedule which is in effect at the given date and time.
+    ///
+    /// The slots in the schedule are times of day local to the Hive account, so they are
+    /// interpreted in the time zone of `at`. A slot stays in effect until the next slot starts,
+    /// so the slot in effect shortly after midnight (or at the start of the week) is usually the
+    /// last slot of the previous day. Returns `None` only if the schedule has no slots at all.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use chrono::{TimeZone, Utc};
+    /// use hive_client::products::Schedule;
+    ///
+    /// let schedule: Schedule = serde_json::from_str(r#"{
+    ///     "monday": [
+    ///         { "start": 390, "value": { "target": 20.0 } },
+    ///         { "start": 1350, "value": { "target": 16.0 } }
+    ///     ]
+    /// }"#).expect("Schedule should be valid");
+    ///
+    /// // A Monday afternoon
+    /// let at = Utc.with_ymd_and_hms(2025, 6, 2, 15, 0, 0).unwrap();
+    ///
+    /// let slot = schedule.current_slot(&at.with_timezone(&chrono_tz::Europe::London))
+    ///     .expect("A slot should be in effect");
+    ///
+    /// // The heating has been at 20°C since 06:30
+    /// assert_eq!(slot.value.target_temperature(), Some(20.0));
+    /// ```
+    #[must_use]
+    pub fn current_slot<Tz: TimeZone>(&self, at: &DateTime<Tz>) -> Option<&ScheduleSlot> {
+        let timezone = at.timezone();
+        let today = at.date_naive();
+
+        // Looking a full 7 days back covers slots later in the day than `at`, on the same day
+        // of the previous week.
+        (0..=7).find_map(|offset| {
+            let date = today.checked_sub_days(Days::new(offset))?;
+
+            let mut slots = self.slots(date.weekday()).iter().collect::<Vec<_>>();
+            slots.sort_by_key(|slot| std::cmp::Reverse(slot.start));
+
+            slots.into_iter().find(|slot| {
+                slot.starts_at()
+                    .and_then(|starts_at| {
+                        timezone
+                            .from_local_datetime(&date.and_time(starts_at))
+                            .earliest()
+                    })
+                    .is_some_and(|starts_at| starts_at <= *at)
+            })
+        })
+    }
+}
+
+/// A change to a product, yielded by [`crate::Client::subscribe`].
+#[derive(Debug)]
+#[non_exhaustive]
+pub struct ProductChange<'a> {
+    /// The product, as it was when the change was seen.
+    pub product: Product<'a>,
+
+    /// The keys (as used in [`Product::state_snapshot`]) of the states and properties which
+    /// changed.
+    ///
+    /// When the product is seen for the first time, every key is included.
+    pub changed: Vec<String>,
+}
+
+impl<'a> ProductChange<'a> {
+    /// Compare the products with the snapshots of those previously seen, returning a change
+    /// for each product which is new or has changed, and updating the snapshots.
+    pub(crate) fn diff(
+        seen: &mut HashMap<String, HashMap<String, String>>,
+        products: Vec<Product<'a>>,
+    ) -> Vec<Self> {
+        products
+            .into_iter()
+            .filter(|product| !matches!(product.data, ProductData::Unknown))
+            .filter_map(|product| {
+                let snapshot = product.state_snapshot();
+                let previous = seen.insert(product.id().to_string(), snapshot.clone());
+
+                let mut changed: Vec<String> = snapshot
+                    .iter()
+                    .filter(|(key, value)| {
+                        previous.as_ref().and_then(|previous| previous.get(*key)) != Some(*value)
+                    })
+                    .map(|(key, _)| key.clone())
+                    .chain(previous.iter().flat_map(|previous| {
+                        previous
+                            .keys()
+                            .filter(|key| !snapshot.contains_key(*key))
+                            .cloned()
+                    }))
+                    .collect();
+                changed.sort();
+
+                (!changed.is_empty()).then_some(Self { product, changed })
+            })
+            .collect()
+    }
+}
+
+/// A Product which is enabled in a Hive account.
+///
+/// For example, a [`ProductData::Heating`], a [`ProductData::HotWater`], etc.
+pub struct Product<'a> {
+    client: &'a Client,
+
+    #[allow(missing_docs)]
+    pub data: ProductData,
 }
 
 impl Debug for Product<'_> {
@@ -213,54 +1501,1078 @@ impl Debug for Product<'_> {
     }
 }
 
-impl Product<'_> {
+impl Product<'_> {
+    #[must_use]
+    pub(crate) const fn new(client: &Client, data: ProductData) -> Product<'_> {
+        Product { client, data }
+    }
+
+    /// The unique ID of the product.
+    pub(crate) const fn id(&self) -> &str {
+        match &self.data {
+            ProductData::HotWater(HotWater { id, .. })
+            | ProductData::Heating(Heating { id, .. })
+            | ProductData::LeakSensor(LeakSensor { id, .. }) => id.as_str(),
+            ProductData::Unknown => "",
+        }
+    }
+
+    /// The type of the product, as used in the paths of the Hive API.
+    pub(crate) const fn r#type(&self) -> &'static str {
+        self.data.r#type()
+    }
+
+    /// Reject a target temperature which is outside of the range accepted by the product,
+    /// preferring the bounds reported by the product itself over the range supported by Hive.
+    fn ensure_valid_target(&self, celsius: f32) -> Result<(), ApiError> {
+        match self
+            .data
+            .as_heating()
+            .and_then(Heating::target_temperature_bounds)
+        {
+            Some((min, max)) if !(min..=max).contains(&celsius) => {
+                Err(ApiError::InvalidTargetTemperature(celsius))
+            }
+            Some(_) => Ok(()),
+            None => ensure_supported_target(celsius),
+        }
+    }
+
+    /// Reject changes to the states of products which are read-only, or (if the client is
+    /// configured to [reject offline changes](Client::reject_offline_changes)) offline.
+    fn ensure_writable(&self) -> Result<(), ApiError> {
+        match &self.data {
+            ProductData::LeakSensor(_) => {
+                Err(ApiError::UnsupportedProduct(self.r#type().to_string()))
+            }
+            ProductData::Heating(Heating { id, properties, .. })
+            | ProductData::HotWater(HotWater { id, properties, .. })
+                if self.client.reject_offline_changes && !properties.is_online =>
+            {
+                Err(ApiError::DeviceOffline(id.clone()))
+            }
+            _ => Ok(()),
+        }
+    }
+
+    /// Reject states which are not applicable to the product, before they are sent to Hive
+    /// (which may otherwise silently ignore them) - for example, a state which doesn't apply to
+    /// the type of product, or a target temperature outside of the range the product accepts.
+    fn ensure_applicable(&self, states: &States) -> Result<(), ApiError> {
+        if let Some(product_type) = self.data.product_type() {
+            product_type.ensure_applicable(states)?;
+        } else if let Some(state) = states.iter().find(|state| !self.data.accepts_state(state)) {
+            // Products whose states can't be set (or aren't known) have no type to check
+            // against.
+            return Err(ApiError::InvalidStateForProduct {
+                state: Box::new(state.clone()),
+                product_type: self.r#type().to_string(),
+            });
+        }
+
+        if let Some(celsius) = states.target_temperature() {
+            self.ensure_valid_target(celsius)?;
+        }
+
+        Ok(())
+    }
+
+    /// Fill in the name of the product's zone, from the zone names reported by the devices
+    /// in the Hive account, if the Hive API didn't already include it.
+    pub(crate) fn resolve_zone_name(&mut self, devices: &[Device]) {
+        let (ProductData::Heating(Heating { properties, .. })
+        | ProductData::HotWater(HotWater { properties, .. })) = &mut self.data
+        else {
+            return;
+        };
+
+        let Some(zone_id) = &properties.zone_id else {
+            return;
+        };
+
+        if properties.zone_name.is_none() {
+            properties.zone_name = devices
+                .iter()
+                .filter_map(|device| device.data.zone())
+                .find(|(id, _)| id == zone_id)
+                .map(|(_, name)| name.to_string());
+        }
+    }
+
+    /// The date and time Hive started (or will start) pre-heating a Heating product, when
+    /// Optimum Start is enabled.
+    ///
+    /// Returns `None` if there is no pre-heat computed, or the product is not a Heating product.
+    #[must_use]
+    pub const fn optimum_start_time(&self) -> Option<DateTime<Utc>> {
+        match &self.data {
+            ProductData::Heating(Heating { properties, .. }) => properties.optimum_start_time,
+            _ => None,
+        }
+    }
+
+    /// The fields returned by the Hive API for the product which are yet to be mapped by the
+    /// crate, regardless of the type of product.
+    ///
+    /// This is empty for products which are themselves yet to be mapped
+    /// ([`ProductData::Unknown`]).
+    #[must_use]
+    pub fn extra(&self) -> &HashMap<String, Value> {
+        static EMPTY: LazyLock<HashMap<String, Value>> = LazyLock::new(HashMap::new);
+
+        match &self.data {
+            ProductData::Heating(Heating { extra, .. })
+            | ProductData::HotWater(HotWater { extra, .. })
+            | ProductData::LeakSensor(LeakSensor { extra, .. }) => extra,
+            ProductData::Unknown => &EMPTY,
+        }
+    }
+
+    /// The target temperature a Heating product will return to once a temporary override
+    /// (such as a boost) ends.
+    ///
+    /// This is distinct from the target temperature in [`Heating::state`], which
+    /// is the target of the override while it's active. Returns `None` if there is no override
+    /// active, or the product is not a Heating product.
+    #[must_use]
+    pub fn scheduled_target(&self) -> Option<f32> {
+        match &self.data {
+            ProductData::Heating(Heating { properties, .. }) => properties
+                .previous
+                .as_ref()
+                .and_then(States::target_temperature),
+            _ => None,
+        }
+    }
+
+    /// A flat snapshot of the states of the product, along with its key properties (such as
+    /// its current temperature, and whether it is online).
+    ///
+    /// Each state is keyed by its name in the Hive API, which gives a uniform payload across
+    /// every type of product - for example, for recording metrics.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use hive_client::authentication::{TrustedDevice, User};
+    ///
+    /// # tokio_test::block_on(async {
+    /// let client = hive_client::Client::new("Home Automation");
+    ///
+    /// let trusted_device = Some(TrustedDevice::new(
+    ///     "device_password",
+    ///     "device_group_key",
+    ///     "device_key"
+    /// ));
+    ///
+    /// client.login(User::new("example@example.com", "example"), trusted_device)
+    ///     .await
+    ///     .expect("Login should succeed");
+    ///
+    /// for product in client.get_products().await.expect("Products should be retrieved") {
+    ///     println!("{:?}", product.state_snapshot());
+    /// }
+    /// # })
+    /// ```
+    #[must_use]
+    pub fn state_snapshot(&self) -> HashMap<String, String> {
+        let (ProductData::Heating(Heating {
+            properties, state, ..
+        })
+        | ProductData::HotWater(HotWater {
+            properties, state, ..
+        })) = &self.data
+        else {
+            return HashMap::new();
+        };
+
+        let mut snapshot: HashMap<String, String> = state
+            .iter()
+            .map(|state| (state.kind().to_string(), state.to_string()))
+            .collect();
+
+        snapshot.insert("online".to_string(), properties.is_online.to_string());
+        snapshot.insert("working".to_string(), properties.is_working.to_string());
+
+        if let Some(temperature) = properties.temperature {
+            snapshot.insert("temperature".to_string(), temperature.to_string());
+        }
+
+        snapshot
+    }
+
+    /// Whether the product's current states match each of the given states.
+    pub(crate) fn reflects(&self, states: &States) -> bool {
+        let snapshot = self.state_snapshot();
+
+        states
+            .iter()
+            .all(|state| snapshot.get(state.kind()) == Some(&state.to_string()))
+    }
+
+    /// Set the state of a product.
+    ///
+    /// For example, setting the target temperature of the Heating product, set the mode
+    /// ([`crate::products::State::Mode`]) of a Hot Water product, etc.
+    ///
+    /// The states are borrowed, so the same set of states can be applied to several products.
+    ///
+    /// Setting several states at once is _not_ atomic - Hive may apply some of the states, and
+    /// still reject the request. Use [`Product::set_states_atomic`] to attempt to restore
+    /// the previous states when this happens.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the state could not be set for the product, or the product is
+    /// read-only (such as a [`ProductData::LeakSensor`]).
+    ///
+    /// If the client is configured to [reject offline changes](Client::reject_offline_changes),
+    /// returns [`ApiError::DeviceOffline`] if the product was offline when it was retrieved.
+    ///
+    /// Returns [`ApiError::InvalidStateForProduct`] if any of the states are not applicable to
+    /// the type of product (see [`ProductData::accepts_state`]).
+    pub async fn set_state(&mut self, states: &States) -> Result<bool, ApiError> {
+        self.ensure_writable()?;
+        self.ensure_applicable(states)?;
+
+        self.client
+            .set_product_state(self.id(), self.r#type(), states)
+            .await
+    }
+
+    /// Set the state of a product, sending a caller-supplied correlation ID with the request.
+    ///
+    /// The correlation ID is returned alongside the ID Hive assigned to the request, so that
+    /// the change can be traced between the caller's logs and Hive.
+    ///
+    /// See [`Product::set_state`].
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use hive_client::authentication::{TrustedDevice, User};
+    /// use hive_client::products::{State, States};
+    ///
+    /// # tokio_test::block_on(async {
+    /// let client = hive_client::Client::new("Home Automation");
+    ///
+    /// let trusted_device = Some(TrustedDevice::new(
+    ///     "device_password",
+    ///     "device_group_key",
+    ///     "device_key"
+    /// ));
+    ///
+    /// client.login(User::new("example@example.com", "example"), trusted_device)
+    ///     .await
+    ///     .expect("Login should succeed");
+    ///
+    /// let mut products = client.get_products()
+    ///     .await
+    ///     .expect("Products should be retrieved");
+    ///
+    /// if let Some(product) = products.first_mut() {
+    ///     let set = product
+    ///         .set_state_with_id(&States(vec![State::TargetTemperature(18.0)]), "user-action-42")
+    ///         .await
+    ///         .expect("Target temperature should be set");
+    ///
+    ///     println!("Hive request {:?} (correlation {:?})", set.request_id, set.correlation_id);
+    /// }
+    /// # })
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns the same errors as [`Product::set_state`].
+    pub async fn set_state_with_id(
+        &mut self,
+        states: &States,
+        correlation_id: &str,
+    ) -> Result<Correlated<bool>, ApiError> {
+        self.ensure_writable()?;
+        self.ensure_applicable(states)?;
+
+        self.client
+            .set_product_state_with_id(self.id(), self.r#type(), states, Some(correlation_id))
+            .await
+    }
+
+    /// Set the state of a product, unless the call is cancelled first.
+    ///
+    /// See [`Product::set_state`]. If the call is cancelled after the request has been sent,
+    /// Hive may still apply the states.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ApiError::Cancelled`] if the call was cancelled, otherwise the same errors as
+    /// [`Product::set_state`].
+    pub async fn set_state_with_cancel(
+        &mut self,
+        states: &States,
+        cancel: &CancellationToken,
+    ) -> Result<bool, ApiError> {
+        self.ensure_writable()?;
+        self.ensure_applicable(states)?;
+
+        Box::pin(
+            self.client.cancellable(
+                cancel,
+                self.client
+                    .set_product_state(self.id(), self.r#type(), states),
+            ),
+        )
+        .await
+    }
+
+    /// Set the state of a product, attempting to restore the previous states if Hive does not
+    /// accept the change.
+    ///
+    /// Before anything is changed, the current value of each of the states being set is taken
+    /// from the product. If the change fails, those values are sent back to Hive, so a change
+    /// which was only partly applied is undone. The restore is best effort - if it fails too,
+    /// it's logged and the original failure is returned.
+    ///
+    /// The previous states are only as fresh as the product, so it should be retrieved with
+    /// [`Client::get_products`] shortly before calling this.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use hive_client::authentication::{TrustedDevice, User};
+    /// use hive_client::products::{Mode, Product, ProductData, State, States};
+    ///
+    /// # tokio_test::block_on(async {
+    /// let client = hive_client::Client::new("Home Automation");
+    ///
+    /// let trusted_device = Some(TrustedDevice::new(
+    ///     "device_password",
+    ///     "device_group_key",
+    ///     "device_key"
+    /// ));
+    ///
+    /// client.login(User::new("example@example.com", "example"), trusted_device)
+    ///     .await
+    ///     .expect("Login should succeed");
+    ///
+    /// let products = client.get_products()
+    ///     .await
+    ///     .expect("Products should be retrieved");
+    ///
+    /// if let Some(mut heating) = products.into_iter().find(|Product { data, .. }| matches!(data, ProductData::Heating { .. })) {
+    ///     let was_set = heating.set_states_atomic(&States(vec![
+    ///         State::Mode(Mode::Manual),
+    ///         State::TargetTemperature(19.0),
+    ///     ]))
+    ///         .await
+    ///         .expect("States should be set");
+    /// }
+    /// # })
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the state could not be set for the product.
+    pub async fn set_states_atomic(&mut self, states: &States) -> Result<bool, ApiError> {
+        // Nothing is sent to Hive if the states are rejected up front, so there's nothing to
+        // restore.
+        self.ensure_writable()?;
+        self.ensure_applicable(states)?;
+
+        let previous_states = self.previous_states(states);
+
+        let result = self.set_state(states).await;
+
+        if !matches!(result, Ok(true)) && !previous_states.is_empty() {
+            match self.set_state(&previous_states).await {
+                Ok(true) => {
+                    log::info!(target: target::API, "Previous states were restored after a failed change.");
+                }
+                Ok(false) | Err(_) => {
+                    log::error!(target: target::API, "Previous states could not be restored after a failed change.");
+                }
+            }
+        }
+
+        result
+    }
+
+    /// The current value of each of the given states, where the product has one.
+    fn previous_states(&self, states: &States) -> States {
+        let (ProductData::Heating(Heating { state, .. })
+        | ProductData::HotWater(HotWater { state, .. })) = &self.data
+        else {
+            return States(vec![]);
+        };
+
+        States(
+            states
+                .iter()
+                .filter_map(|new_state| {
+                    state
+                        .iter()
+                        .find(|current_state| current_state.matches_kind(new_state))
+                        .cloned()
+                })
+                .collect(),
+        )
+    }
+
+    /// Set the state of a product, and update the product with the new state returned by Hive.
+    ///
+    /// This is the same as [`Product::set_state`], except the updated product data is read from
+    /// the response, so no follow-up call to [`Client::get_products`] is needed to confirm the
+    /// change took effect.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use hive_client::authentication::{TrustedDevice, User};
+    /// use hive_client::products::{Product, ProductData, State, States};
+    ///
+    /// # tokio_test::block_on(async {
+    /// let client = hive_client::Client::new("Home Automation");
+    ///
+    /// let trusted_device = Some(TrustedDevice::new(
+    ///     "device_password",
+    ///     "device_group_key",
+    ///     "device_key"
+    /// ));
+    ///
+    /// client.login(User::new("example@example.com", "example"), trusted_device)
+    ///     .await
+    ///     .expect("Login should succeed");
+    ///
+    /// let products = client.get_products()
+    ///     .await
+    ///     .expect("Products should be retrieved");
+    ///
+    /// if let Some(mut heating) = products.into_iter().find(|Product { data, .. }| matches!(data, ProductData::Heating { .. })) {
+    ///     let data = heating.set_state_returning(&States(vec!(State::TargetTemperature(18.0))))
+    ///         .await
+    ///         .expect("Product state should be set");
+    ///
+    ///     println!("{:?}", data);
+    /// }
+    /// # })
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the state could not be set for the product, or the response from
+    /// Hive could not be decoded.
+    pub async fn set_state_returning(&mut self, states: &States) -> Result<&ProductData, ApiError> {
+        self.ensure_writable()?;
+        self.ensure_applicable(states)?;
+
+        self.data = self
+            .client
+            .set_product_state_returning(self.id(), self.r#type(), states)
+            .await?;
+
+        Ok(&self.data)
+    }
+
+    /// Set a temporary target temperature, which lasts until a given date and time.
+    ///
+    /// This boosts the product to the target temperature, after which it returns to its
+    /// previous mode (for example, back to its schedule). This applies to Heating products.
+    ///
+    /// Hive only accepts boosts of certain lengths (see [`BoostDuration`]), so the boost lasts
+    /// for the shortest of them which reaches `until` - and may end a little after it.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use chrono::{Duration, Utc};
+    /// use hive_client::authentication::{TrustedDevice, User};
+    /// use hive_client::products::{Product, ProductData};
+    ///
+    /// # tokio_test::block_on(async {
+    /// let client = hive_client::Client::new("Home Automation");
+    ///
+    /// let trusted_device = Some(TrustedDevice::new(
+    ///     "device_password",
+    ///     "device_group_key",
+    ///     "device_key"
+    /// ));
+    ///
+    /// client.login(User::new("example@example.com", "example"), trusted_device)
+    ///     .await
+    ///     .expect("Login should succeed");
+    ///
+    /// let products = client.get_products()
+    ///     .await
+    ///     .expect("Products should be retrieved");
+    ///
+    /// if let Some(mut heating) = products.into_iter().find(|Product { data, .. }| matches!(data, ProductData::Heating { .. })) {
+    ///     // Warm to 21°C for the next two hours, then return to the schedule.
+    ///     let was_set = heating.set_target_until(21.0, Utc::now() + Duration::hours(2))
+    ///         .await
+    ///         .expect("Temporary target should be set");
+    /// }
+    /// # })
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the target temperature is outside of the range accepted by the
+    /// product, if `until` is not in the future (or is further away than the longest
+    /// [`BoostDuration`]), or if the state could not be set for the product.
+    pub async fn set_target_until(
+        &mut self,
+        celsius: f32,
+        until: DateTime<Utc>,
+    ) -> Result<bool, ApiError> {
+        let duration = (until - self.client.clock.now())
+            .to_std()
+            .ok()
+            .filter(|duration| !duration.is_zero())
+            .and_then(BoostDuration::at_least)
+            .ok_or(ApiError::InvalidBoostEnd(until))?;
+
+        self.set_state(&States(vec![
+            State::Mode(Mode::Boost),
+            State::Boost(Some(Boost::Minutes(duration.minutes()))),
+            State::TargetTemperature(celsius),
+        ]))
+        .await
+    }
+
+    /// Advance a Heating product to the next period of its schedule early, for example to
+    /// start the evening heating ahead of time.
+    ///
+    /// The product stays in [`Mode::Schedule`], with the target temperature of the next slot in
+    /// its schedule applied now. Hive holds a target set while following a schedule until the
+    /// next scheduled change - which is when the next period would have started anyway - so
+    /// the product carries on following its schedule from there. Use
+    /// [`Product::cancel_advance`] to return to the current period of the schedule.
+    ///
+    /// The slots in the schedule are interpreted in the given time zone, which should be the
+    /// time zone of the Hive account (see [`crate::Client::get_account_timezone`]).
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use hive_client::authentication::{TrustedDevice, User};
+    /// use hive_client::products::{Product, ProductData};
+    ///
+    /// # tokio_test::block_on(async {
+    /// let client = hive_client::Client::new("Home Automation");
+    ///
+    /// let trusted_device = Some(TrustedDevice::new(
+    ///     "device_password",
+    ///     "device_group_key",
+    ///     "device_key"
+    /// ));
+    ///
+    /// client.login(User::new("example@example.com", "example"), trusted_device)
+    ///     .await
+    ///     .expect("Login should succeed");
+    ///
+    /// let timezone = client.get_account_timezone()
+    ///     .await
+    ///     .expect("Account time zone should be retrieved");
+    ///
+    /// let products = client.get_products()
+    ///     .await
+    ///     .expect("Products should be retrieved");
+    ///
+    /// if let Some(mut heating) = products.into_iter().find(|Product { data, .. }| matches!(data, ProductData::Heating { .. })) {
+    ///     let was_advanced = heating.advance(&timezone)
+    ///         .await
+    ///         .expect("Heating should be advanced");
+    /// }
+    /// # })
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the product is not a Heating product, if it is not following a
+    /// schedule, or if the state could not be set for the product.
+    pub async fn advance<Tz: TimeZone + Sync>(&mut self, tz: &Tz) -> Result<bool, ApiError> {
+        let target = self
+            .schedule()?
+            .next_change(&self.client.clock.now().with_timezone(tz))
+            .and_then(|change| change.slot.value.target_temperature())
+            .ok_or(ApiError::NotScheduled)?;
+
+        self.set_state(&States(vec![
+            State::Mode(Mode::Schedule),
+            State::TargetTemperature(target),
+        ]))
+        .await
+    }
+
+    /// Cancel an [advance](Product::advance), by restoring the target temperature of the
+    /// current period of a Heating product's schedule.
+    ///
+    /// The slots in the schedule are interpreted in the given time zone, which should be the
+    /// time zone of the Hive account (see [`crate::Client::get_account_timezone`]).
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the product is not a Heating product, if it is not following a
+    /// schedule, or if the state could not be set for the product.
+    pub async fn cancel_advance<Tz: TimeZone + Sync>(&mut self, tz: &Tz) -> Result<bool, ApiError> {
+        let target = self
+            .schedule()?
+            .current_slot(&self.client.clock.now().with_timezone(tz))
+            .and_then(|slot| slot.value.target_temperature())
+            .ok_or(ApiError::NotScheduled)?;
+
+        self.set_state(&States(vec![
+            State::Mode(Mode::Schedule),
+            State::TargetTemperature(target),
+        ]))
+        .await
+    }
+
+    /// The schedule a Heating product is currently following.
+    fn schedule(&self) -> Result<&Schedule, ApiError> {
+        let ProductData::Heating(Heating { state, .. }) = &self.data else {
+            return Err(ApiError::UnsupportedProduct(self.r#type().to_string()));
+        };
+
+        match (state.mode(), state.schedule()) {
+            (Some(Mode::Schedule), Some(schedule)) => Ok(schedule),
+            _ => Err(ApiError::NotScheduled),
+        }
+    }
+
+    /// Set the target temperature of a Heating product, in Fahrenheit.
+    ///
+    /// Hive only accepts target temperatures in Celsius, so the temperature is converted and
+    /// rounded to the nearest half degree Celsius before being set.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use hive_client::authentication::{TrustedDevice, User};
+    /// use hive_client::products::{Product, ProductData};
+    ///
+    /// # tokio_test::block_on(async {
+    /// let client = hive_client::Client::new("Home Automation");
+    ///
+    /// let trusted_device = Some(TrustedDevice::new(
+    ///     "device_password",
+    ///     "device_group_key",
+    ///     "device_key"
+    /// ));
+    ///
+    /// client.login(User::new("example@example.com", "example"), trusted_device)
+    ///     .await
+    ///     .expect("Login should succeed");
+    ///
+    /// let products = client.get_products()
+    ///     .await
+    ///     .expect("Products should be retrieved");
+    ///
+    /// if let Some(mut heating) = products.into_iter().find(|Product { data, .. }| matches!(data, ProductData::Heating { .. })) {
+    ///     // Sets a target of 21°C
+    ///     heating.set_target_temperature_fahrenheit(70.0)
+    ///         .await
+    ///         .expect("Target temperature should be set");
+    /// }
+    /// # })
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the converted target temperature is outside of the range accepted by
+    /// the product, or if the state could not be set for the product.
+    pub async fn set_target_temperature_fahrenheit(
+        &mut self,
+        fahrenheit: f32,
+    ) -> Result<bool, ApiError> {
+        let celsius = round_to_half_degree(fahrenheit_to_celsius(fahrenheit));
+
+        self.set_state(&States(vec![State::TargetTemperature(celsius)]))
+            .await
+    }
+
+    /// Switch a Heating product to [`Mode::Manual`] with a given target temperature, in
+    /// Celsius.
+    ///
+    /// The mode and target are sent to Hive together in a single request, so there's no
+    /// window where the product has switched mode but is still heating to its previous
+    /// target. As with [`Product::set_state`], Hive may still apply only some of the states if
+    /// it rejects the request - use [`Product::set_states_atomic`] with
+    /// [`States::manual_target`] to restore the previous states when this happens.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use hive_client::authentication::{TrustedDevice, User};
+    /// use hive_client::products::{Product, ProductData};
+    ///
+    /// # tokio_test::block_on(async {
+    /// let client = hive_client::Client::new("Home Automation");
+    ///
+    /// let trusted_device = Some(TrustedDevice::new(
+    ///     "device_password",
+    ///     "device_group_key",
+    ///     "device_key"
+    /// ));
+    ///
+    /// client.login(User::new("example@example.com", "example"), trusted_device)
+    ///     .await
+    ///     .expect("Login should succeed");
+    ///
+    /// let products = client.get_products()
+    ///     .await
+    ///     .expect("Products should be retrieved");
+    ///
+    /// if let Some(mut heating) = products.into_iter().find(|Product { data, .. }| matches!(data, ProductData::Heating { .. })) {
+    ///     heating.set_manual_target(19.5)
+    ///         .await
+    ///         .expect("Manual target should be set");
+    /// }
+    /// # })
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the target temperature is outside of the range accepted by the product,
+    /// or if the states could not be set for the product.
+    pub async fn set_manual_target(&mut self, celsius: f32) -> Result<bool, ApiError> {
+        self.set_state(&States(vec![
+            State::Mode(Mode::Manual),
+            State::TargetTemperature(celsius),
+        ]))
+        .await
+    }
+
+    /// Boost the product for a given length of time, after which it returns to its
+    /// previous mode.
+    ///
+    /// For a Heating product, a target temperature to boost to should be provided.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use hive_client::authentication::{TrustedDevice, User};
+    /// use hive_client::products::{BoostDuration, Product, ProductData};
+    ///
+    /// # tokio_test::block_on(async {
+    /// let client = hive_client::Client::new("Home Automation");
+    ///
+    /// let trusted_device = Some(TrustedDevice::new(
+    ///     "device_password",
+    ///     "device_group_key",
+    ///     "device_key"
+    /// ));
+    ///
+    /// client.login(User::new("example@example.com", "example"), trusted_device)
+    ///     .await
+    ///     .expect("Login should succeed");
+    ///
+    /// let products = client.get_products()
+    ///     .await
+    ///     .expect("Products should be retrieved");
+    ///
+    /// if let Some(mut hot_water) = products.into_iter().find(|Product { data, .. }| matches!(data, ProductData::HotWater { .. })) {
+    ///     let was_boosted = hot_water.boost(BoostDuration::OneHour, None)
+    ///         .await
+    ///         .expect("Hot water should be boosted");
+    /// }
+    /// # })
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the target temperature is outside of the range accepted by the
+    /// product, or if the state could not be set for the product.
+    pub async fn boost(
+        &mut self,
+        duration: BoostDuration,
+        celsius: Option<f32>,
+    ) -> Result<bool, ApiError> {
+        let mut states = vec![
+            State::Mode(Mode::Boost),
+            State::Boost(Some(Boost::Minutes(duration.minutes()))),
+        ];
+
+        if let Some(celsius) = celsius {
+            states.push(State::TargetTemperature(celsius));
+        }
+
+        self.set_state(&States(states)).await
+    }
+
+    /// Turn a Hot Water product on continuously (or off), rather than following its schedule.
+    ///
+    /// This puts the product into [`Mode::Manual`], with the matching [`State::Status`].
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use hive_client::authentication::{TrustedDevice, User};
+    /// use hive_client::products::{Product, ProductData};
+    ///
+    /// # tokio_test::block_on(async {
+    /// let client = hive_client::Client::new("Home Automation");
+    ///
+    /// let trusted_device = Some(TrustedDevice::new(
+    ///     "device_password",
+    ///     "device_group_key",
+    ///     "device_key"
+    /// ));
+    ///
+    /// client.login(User::new("example@example.com", "example"), trusted_device)
+    ///     .await
+    ///     .expect("Login should succeed");
+    ///
+    /// let products = client.get_products()
+    ///     .await
+    ///     .expect("Products should be retrieved");
+    ///
+    /// if let Some(mut hot_water) = products.into_iter().find(|Product { data, .. }| matches!(data, ProductData::HotWater { .. })) {
+    ///     let is_on = hot_water.set_hot_water_on(true)
+    ///         .await
+    ///         .expect("Hot water should be turned on");
+    /// }
+    /// # })
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the product is not a Hot Water product, or if the state could not
+    /// be set for the product.
+    pub async fn set_hot_water_on(&mut self, on: bool) -> Result<bool, ApiError> {
+        let ProductData::HotWater { .. } = self.data else {
+            return Err(ApiError::UnsupportedProduct(self.r#type().to_string()));
+        };
+
+        self.set_state(&States(vec![
+            State::Mode(Mode::Manual),
+            State::Status(if on {
+                HeatingStatus::On
+            } else {
+                HeatingStatus::Off
+            }),
+        ]))
+        .await
+    }
+
+    /// Get the usage history of the product over a given date range.
+    ///
+    /// For example, the periods of time a Heating product was running for.
+    ///
+    /// The shape of the history varies between products, so it is returned as-is from the
+    /// Hive API.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use chrono::{Duration, Utc};
+    /// use hive_client::authentication::{TrustedDevice, User};
+    /// use hive_client::products::{Product, ProductData};
+    ///
+    /// # tokio_test::block_on(async {
+    /// let client = hive_client::Client::new("Home Automation");
+    ///
+    /// let trusted_device = Some(TrustedDevice::new(
+    ///     "device_password",
+    ///     "device_group_key",
+    ///     "device_key"
+    /// ));
+    ///
+    /// client.login(User::new("example@example.com", "example"), trusted_device)
+    ///     .await
+    ///     .expect("Login should succeed");
+    ///
+    /// let products = client.get_products()
+    ///     .await
+    ///     .expect("Products should be retrieved");
+    ///
+    /// if let Some(heating) = products.into_iter().find(|Product { data, .. }| matches!(data, ProductData::Heating { .. })) {
+    ///     let now = Utc::now();
+    ///
+    ///     let usage = heating.get_usage(now - Duration::days(7)..now)
+    ///        .await
+    ///        .expect("Usage should be retrieved");
+    ///
+    ///     println!("{:?}", usage);
+    /// }
+    /// # })
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the usage history could not be retrieved for the product.
+    pub async fn get_usage(&self, range: Range<DateTime<Utc>>) -> Result<UsageSeries, ApiError> {
+        self.client
+            .get_product_usage(self.id(), self.r#type(), range)
+            .await
+    }
+}
+
+/// A heating zone in a Hive account, made up of the Heating products (for example, the
+/// thermostat and any radiator valves) which share a zone-level setpoint.
+///
+/// Returned by [`crate::Client::get_heating_zones`].
+#[derive(Debug)]
+pub struct HeatingZone<'a> {
+    /// The unique ID of the zone.
+    ///
+    /// Heating products which aren't assigned to a zone make up a zone of their own, identified
+    /// by the ID of the product.
+    pub id: String,
+
+    /// The Heating products which are members of the zone.
+    pub products: Vec<Product<'a>>,
+}
+
+impl<'a> HeatingZone<'a> {
+    /// Group Heating products into their zones, in the order each zone is first seen.
+    ///
+    /// Any products which are not Heating products are ignored.
+    pub(crate) fn group(products: Vec<Product<'a>>) -> Vec<Self> {
+        let mut zones: Vec<Self> = Vec::new();
+
+        for product in products {
+            let ProductData::Heating(Heating { id, properties, .. }) = &product.data else {
+                continue;
+            };
+
+            let zone_id = properties.zone_id.as_ref().unwrap_or(id);
+
+            if let Some(zone) = zones.iter_mut().find(|zone| &zone.id == zone_id) {
+                zone.products.push(product);
+            } else {
+                zones.push(Self {
+                    id: zone_id.clone(),
+                    products: vec![product],
+                });
+            }
+        }
+
+        zones
+    }
+
+    fn states(&self) -> impl Iterator<Item = &States> {
+        self.products
+            .iter()
+            .filter_map(|product| match &product.data {
+                ProductData::Heating(Heating { state, .. }) => Some(state),
+                _ => None,
+            })
+    }
+
+    /// The name of the zone, as shown in the Hive app.
     #[must_use]
-    pub(crate) const fn new(client: &Client, data: ProductData) -> Product<'_> {
-        Product { client, data }
+    pub fn name(&self) -> Option<&str> {
+        self.states().find_map(States::name)
+    }
+
+    /// The current temperature of the zone, averaged across each of the members which report
+    /// a temperature.
+    #[must_use]
+    #[allow(clippy::cast_precision_loss)]
+    pub fn current_temperature(&self) -> Option<f32> {
+        let temperatures: Vec<f32> = self
+            .products
+            .iter()
+            .filter_map(|product| match &product.data {
+                ProductData::Heating(Heating { properties, .. }) => properties.temperature,
+                _ => None,
+            })
+            .collect();
+
+        if temperatures.is_empty() {
+            return None;
+        }
+
+        Some(temperatures.iter().sum::<f32>() / temperatures.len() as f32)
+    }
+
+    /// The target temperature of the zone.
+    #[must_use]
+    pub fn target_temperature(&self) -> Option<f32> {
+        self.states().find_map(States::target_temperature)
+    }
+
+    /// The mode of the zone.
+    #[must_use]
+    pub fn mode(&self) -> Option<&Mode> {
+        self.states().find_map(States::mode)
     }
 
-    /// Set the state of a product.
+    /// Set the target temperature of the zone, by setting the target on each of its members.
     ///
-    /// For example, setting the target temperature of the Heating product, set the mode
-    /// ([`crate::products::State::Mode`]) of a Hot Water product, etc.
+    /// The target is applied to each of the members concurrently, and the outcome for each
+    /// member is returned in the same order as [`HeatingZone::products`].
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use hive_client::authentication::{TrustedDevice, User};
+    ///
+    /// # tokio_test::block_on(async {
+    /// let client = hive_client::Client::new("Home Automation");
+    ///
+    /// let trusted_device = Some(TrustedDevice::new(
+    ///     "device_password",
+    ///     "device_group_key",
+    ///     "device_key"
+    /// ));
+    ///
+    /// client.login(User::new("example@example.com", "example"), trusted_device)
+    ///     .await
+    ///     .expect("Login should succeed");
+    ///
+    /// let mut zones = client.get_heating_zones()
+    ///     .await
+    ///     .expect("Heating zones should be retrieved");
+    ///
+    /// for zone in &mut zones {
+    ///     zone.set_target_temperature(19.5)
+    ///         .await
+    ///         .expect("Zone target should be set");
+    /// }
+    /// # })
+    /// ```
     ///
     /// # Errors
     ///
-    /// Returns an error if the state could not be set for the product.
-    pub async fn set_state(&mut self, states: States) -> Result<bool, ApiError> {
-        self.client
-            .set_product_state(
-                match &self.data {
-                    ProductData::HotWater { id, .. } | ProductData::Heating { id, .. } => id,
-                    ProductData::Unknown => "",
-                },
-                match &self.data {
-                    ProductData::Heating { .. } => "heating",
-                    ProductData::HotWater { .. } => "hotwater",
-                    ProductData::Unknown => "unknown",
-                },
-                states,
-            )
-            .await
+    /// Returns an error if the target temperature is outside of the range supported by Hive
+    /// ([`ApiError::InvalidTargetTemperature`]), in which case no member is changed.
+    ///
+    /// A failure to set the target on an individual member (including a target outside of the
+    /// narrower range a member reports that it accepts) is instead reported as `false` in its
+    /// position in the returned list.
+    pub async fn set_target_temperature(&mut self, celsius: f32) -> Result<Vec<bool>, ApiError> {
+        ensure_supported_target(celsius)?;
+
+        let states = States(vec![State::TargetTemperature(celsius)]);
+
+        Ok(join_all(self.products.iter_mut().map(|product| async {
+            product.set_state(&states).await.unwrap_or_else(|err| {
+                log::error!(target: target::API, "Failed to set the target temperature of a zone member: {err}");
+                false
+            })
+        }))
+        .await)
     }
 }
 
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(transparent)]
+/// A series of usage history for a Hive product, as returned by [`Product::get_usage`].
+pub struct UsageSeries(pub Value);
+
+/// The keys Hive may wrap the list of products under, rather than returning a bare array.
+const LIST_KEYS: &[&str] = &["products", "data"];
+
 impl HiveApi {
-    pub(crate) async fn get_product_data(
+    /// Retrieve the products in the Hive account, decoding each as the given type - for
+    /// example, as [`ProductData`] or as the raw JSON returned by the Hive API.
+    pub(crate) async fn get_product_data<T: DeserializeOwned>(
         &self,
         tokens: &Tokens,
-    ) -> Result<Vec<ProductData>, ApiError> {
-        let response = self
-            .client
-            .get(get_base_url(&Url::Products))
-            .header("Authorization", &tokens.id_token)
-            .send()
-            .await;
-
-        response?
-            .json::<Vec<ProductData>>()
+    ) -> Result<Vec<T>, ApiError> {
+        self.fetch_list(tokens, &Url::Products, "products", LIST_KEYS)
             .await
-            .map_err(ApiError::from)
     }
 
     pub(crate) async fn set_product_state(
@@ -268,19 +2580,1020 @@ impl HiveApi {
         tokens: &Tokens,
         id: &str,
         r#type: &str,
-        states: States,
-    ) -> Result<bool, ApiError> {
+        states: &States,
+        correlation_id: Option<&str>,
+    ) -> Result<Correlated<bool>, ApiError> {
+        #[cfg(feature = "fixtures")]
+        if let Some(fixtures) = &self.fixtures {
+            fixtures.record(RecordedCall::SetState {
+                product_id: id.to_string(),
+                product_type: r#type.to_string(),
+                states: states.clone(),
+            });
+
+            return Ok(Correlated {
+                value: true,
+                correlation_id: correlation_id.map(ToString::to_string),
+                request_id: None,
+            });
+        }
+
+        let request = self
+            .client
+            .post(get_base_url(&Url::Node {
+                id: Some(id),
+                r#type: Some(r#type),
+            }))
+            .body(serde_json::to_string(states)?)
+            .header("Authorization", &tokens.id_token);
+
+        send_correlated(request, correlation_id)
+            .await?
+            .try_map(|response| Ok(ensure_authorized(response)?.status() == StatusCode::OK))
+    }
+
+    pub(crate) async fn set_product_state_returning(
+        &self,
+        tokens: &Tokens,
+        id: &str,
+        r#type: &str,
+        states: &States,
+    ) -> Result<ProductData, ApiError> {
+        #[cfg(feature = "fixtures")]
+        if let Some(fixtures) = &self.fixtures {
+            let products = unwrap_list(fixtures.load("products")?, LIST_KEYS);
+
+            return fixtures.set_state_returning(products, id, r#type, states);
+        }
+
         let response = self
             .client
             .post(get_base_url(&Url::Node {
                 id: Some(id),
                 r#type: Some(r#type),
             }))
-            .body(serde_json::to_string(&states)?)
+            .body(serde_json::to_string(states)?)
             .header("Authorization", &tokens.id_token)
             .send()
             .await?;
 
-        Ok(response.status() == StatusCode::OK)
+        let mut node = self.decode_json::<Value>(response).await?;
+
+        // The updated node isn't guaranteed to include its type, which is needed to decode it
+        // into the correct product.
+        if let Value::Object(node) = &mut node {
+            node.entry("type")
+                .or_insert_with(|| Value::String(r#type.to_string()));
+        }
+
+        Ok(ProductData::deserialize(node)?)
+    }
+
+    pub(crate) async fn get_product_usage(
+        &self,
+        tokens: &Tokens,
+        id: &str,
+        r#type: &str,
+        range: Range<DateTime<Utc>>,
+    ) -> Result<UsageSeries, ApiError> {
+        #[cfg(feature = "fixtures")]
+        if let Some(fixtures) = &self.fixtures {
+            return Ok(UsageSeries(fixtures.load("usage")?));
+        }
+
+        let response = self
+            .client
+            .get(get_base_url(&Url::History { r#type, id }))
+            .query(&[
+                ("start", range.start.timestamp_millis()),
+                ("end", range.end.timestamp_millis()),
+            ])
+            .header("Authorization", &tokens.id_token)
+            .send()
+            .await;
+
+        self.decode_json(response?).await
+    }
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+
+    /// A Heating product with the given properties and states, for use in tests.
+    pub fn heating(client: &Client, props: Value, state: Value) -> Product<'_> {
+        let mut product = serde_json::json!({ "type": "heating", "id": "heating", "created": 0 });
+        product["props"] = props;
+        product["state"] = state;
+
+        Product::new(
+            client,
+            serde_json::from_value(product).expect("Product should deserialize"),
+        )
+    }
+
+    fn schedule() -> Schedule {
+        serde_json::from_str(
+            r#"{
+                "monday": [
+                    { "start": 1350, "value": { "target": 16.0 } },
+                    { "start": 390, "value": { "target": 20.0 } }
+                ],
+                "wednesday": [
+                    { "start": 420, "value": { "target": 19.5 } }
+                ]
+            }"#,
+        )
+        .expect("Schedule should deserialize")
+    }
+
+    #[test]
+    fn test_next_change_wraps_across_midnight() {
+        let schedule = schedule();
+
+        // A Monday, after the final slot of the day
+        let from = Utc.with_ymd_and_hms(2025, 6, 2, 23, 0, 0).unwrap();
+
+        let change = schedule.next_change(&from).expect("A change should exist");
+
+        assert_eq!(
+            change.at,
+            Utc.with_ymd_and_hms(2025, 6, 4, 7, 0, 0).unwrap()
+        );
+        assert_eq!(change.slot.start, 420);
+    }
+
+    #[test]
+    fn test_next_change_wraps_across_the_week() {
+        let schedule = schedule();
+
+        // A Wednesday, after the only slot of the day
+        let from = Utc.with_ymd_and_hms(2025, 6, 4, 7, 0, 0).unwrap();
+
+        let change = schedule.next_change(&from).expect("A change should exist");
+
+        assert_eq!(
+            change.at,
+            Utc.with_ymd_and_hms(2025, 6, 9, 6, 30, 0).unwrap()
+        );
+        assert_eq!(change.slot.start, 390);
+    }
+
+    #[test]
+    fn test_current_slot_started_the_previous_day() {
+        let schedule = schedule();
+
+        // A Tuesday, before any slots of the day (there are none)
+        let at = Utc.with_ymd_and_hms(2025, 6, 3, 2, 0, 0).unwrap();
+
+        let slot = schedule.current_slot(&at).expect("A slot should exist");
+
+        assert_eq!(slot.start, 1350);
+    }
+
+    #[test]
+    fn test_current_slot_started_the_previous_week() {
+        let schedule = schedule();
+
+        // A Monday, before the first slot of the week
+        let at = Utc.with_ymd_and_hms(2025, 6, 2, 5, 0, 0).unwrap();
+
+        let slot = schedule.current_slot(&at).expect("A slot should exist");
+
+        assert_eq!(slot.start, 420);
+        assert!(Schedule::default().current_slot(&at).is_none());
+    }
+
+    #[test]
+    fn test_current_slot_is_found_in_the_local_time_zone() {
+        let schedule = schedule();
+
+        // 07:00 on a Monday in London (BST), which is still before 06:30 in UTC
+        let at = Utc.with_ymd_and_hms(2025, 6, 2, 6, 0, 0).unwrap();
+
+        let slot = schedule
+            .current_slot(&at.with_timezone(&chrono_tz::Europe::London))
+            .expect("A slot should exist");
+
+        assert_eq!(slot.start, 390);
+        assert_eq!(slot.value.target_temperature(), Some(20.0));
+    }
+
+    #[test]
+    fn test_boost_deserializes_from_minutes_or_flag() {
+        for (payload, expected) in [
+            (r#"{ "boost": 30 }"#, Some(Boost::Minutes(30))),
+            (r#"{ "boost": true }"#, Some(Boost::Active(true))),
+            (r#"{ "boost": false }"#, Some(Boost::Active(false))),
+            (r#"{ "boost": null }"#, None),
+        ] {
+            let states: States = serde_json::from_str(payload).expect("States should deserialize");
+
+            assert!(
+                matches!(states.first(), Some(State::Boost(minutes)) if *minutes == expected),
+                "{payload} should deserialize to {expected:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_next_change_of_empty_schedule() {
+        let from = Utc.with_ymd_and_hms(2025, 6, 2, 12, 0, 0).unwrap();
+
+        assert!(Schedule::default().next_change(&from).is_none());
+    }
+
+    #[test]
+    fn test_boost_duration_only_accepts_supported_durations() {
+        assert_eq!(
+            BoostDuration::try_from(Duration::from_secs(2 * 60 * 60)).ok(),
+            Some(BoostDuration::TwoHours)
+        );
+        assert!(matches!(
+            BoostDuration::try_from(Duration::from_secs(45 * 60)),
+            Err(ApiError::InvalidBoostDuration(_))
+        ));
+        assert_eq!(
+            BoostDuration::nearest(Duration::from_secs(40 * 60)),
+            BoostDuration::HalfHour
+        );
+    }
+
+    #[test]
+    fn test_humidity_deserialization() {
+        for (humidity, expected) in [
+            ("", None),
+            (r#", "humidity": null"#, None),
+            (r#", "humidity": 48"#, Some(48)),
+            (r#", "humidity": 47.6"#, Some(48)),
+        ] {
+            let properties: Properties = serde_json::from_str(&format!(
+                r#"{{ "online": true, "working": true{humidity} }}"#
+            ))
+            .expect("Properties should deserialize");
+
+            assert_eq!(properties.humidity, expected, "{humidity:?}");
+        }
+    }
+
+    #[test]
+    fn test_temperatures_deserialize_from_numbers_or_strings() {
+        for (temperature, expected) in [
+            ("", None),
+            (r#", "temperature": null"#, None),
+            (r#", "temperature": 19.5"#, Some(19.5)),
+            (r#", "temperature": "19.5""#, Some(19.5)),
+        ] {
+            let properties: Properties = serde_json::from_str(&format!(
+                r#"{{ "online": true, "working": true{temperature} }}"#
+            ))
+            .expect("Properties should deserialize");
+
+            assert_eq!(properties.temperature, expected, "{temperature:?}");
+        }
+
+        let states: States =
+            serde_json::from_str(r#"{ "target": "21" }"#).expect("States should deserialize");
+        assert_eq!(states.target_temperature(), Some(21.0));
+
+        assert!(serde_json::from_str::<States>(r#"{ "target": "warm" }"#).is_err());
+    }
+
+    #[test]
+    fn test_only_changed_products_are_reported() {
+        let client = Client::new("Home Automation");
+
+        let heating = |id: &str, target: f32| {
+            Product::new(
+                &client,
+                serde_json::from_value(serde_json::json!({
+                    "type": "heating",
+                    "id": id,
+                    "created": 0,
+                    "props": { "online": true, "working": true },
+                    "state": { "target": target }
+                }))
+                .expect("Product should deserialize"),
+            )
+        };
+
+        let mut seen = HashMap::new();
+
+        let changes = ProductChange::diff(&mut seen, vec![heating("a", 18.0), heating("b", 18.0)]);
+        assert_eq!(changes.len(), 2);
+
+        let changes = ProductChange::diff(&mut seen, vec![heating("a", 18.0), heating("b", 21.0)]);
+        assert_eq!(changes.len(), 1);
+        assert_eq!(changes[0].product.id(), "b");
+        assert_eq!(changes[0].changed, ["target"]);
+    }
+
+    #[test]
+    fn test_sensor_temperatures() {
+        let properties: Properties = serde_json::from_str(
+            r#"{
+                "online": true,
+                "working": true,
+                "temperature": 19.5,
+                "sensors": [
+                    { "id": "thermostat", "temperature": 19.0 },
+                    { "id": "valve", "temperature": "20.0" },
+                    { "id": "offline-valve", "temperature": null }
+                ]
+            }"#,
+        )
+        .expect("Properties should deserialize");
+
+        assert_eq!(properties.temperature, Some(19.5));
+        assert_eq!(
+            properties.sensor_temperatures,
+            [
+                ("thermostat".to_string(), 19.0),
+                ("valve".to_string(), 20.0)
+            ]
+        );
+
+        let properties: Properties =
+            serde_json::from_str(r#"{ "online": true, "working": true, "temperature": 19.5 }"#)
+                .expect("Properties should deserialize");
+
+        assert!(properties.sensor_temperatures.is_empty());
+    }
+
+    #[test]
+    fn test_optimum_start_time() {
+        let client = Client::new("Home Automation");
+
+        let product = heating(
+            &client,
+            serde_json::json!({ "online": true, "working": true, "optimumStartTime": 1_748_844_720_000_u64 }),
+            serde_json::json!({ "mode": "SCHEDULE", "optimumStart": true, "target": 21.0 }),
+        );
+
+        assert_eq!(
+            product.optimum_start_time(),
+            Utc.with_ymd_and_hms(2025, 6, 2, 6, 12, 0).single()
+        );
+    }
+
+    #[test]
+    fn test_extra_fields_are_available_for_every_product() {
+        let client = Client::new("Home Automation");
+
+        let product = Product::new(
+            &client,
+            serde_json::from_str(
+                r#"{
+                    "type": "hotwater",
+                    "id": "hot-water",
+                    "created": 0,
+                    "parent": "boiler-module",
+                    "props": { "online": true, "working": true },
+                    "state": { "mode": "OFF" }
+                }"#,
+            )
+            .expect("Product should deserialize"),
+        );
+
+        assert_eq!(product.extra()["parent"], "boiler-module");
+        assert!(
+            Product::new(&client, ProductData::Unknown)
+                .extra()
+                .is_empty()
+        );
+    }
+
+    #[test]
+    fn test_zone_names_are_resolved_from_devices() {
+        let client = Client::new("Home Automation");
+
+        let mut product = heating(
+            &client,
+            serde_json::json!({ "zone": "living-room", "online": true, "working": true }),
+            serde_json::json!({ "mode": "SCHEDULE" }),
+        );
+
+        let devices = vec![Device::new(
+            serde_json::from_str(
+                r#"{
+                    "type": "thermostatui",
+                    "id": "thermostat",
+                    "lastSeen": 0,
+                    "created": 0,
+                    "props": { "online": true, "zone": "living-room" },
+                    "state": { "name": "Thermostat", "zoneName": "Living Room" }
+                }"#,
+            )
+            .expect("Device should deserialize"),
+        )];
+
+        product.resolve_zone_name(&devices);
+
+        let ProductData::Heating(Heating { properties, .. }) = &product.data else {
+            panic!("Expected a heating product")
+        };
+
+        assert_eq!(properties.zone_name.as_deref(), Some("Living Room"));
+    }
+
+    #[test]
+    fn test_heating_products_are_grouped_into_zones() {
+        let client = Client::new("Home Automation");
+
+        let heating = |id: &str, zone: Option<&str>, temperature: f32| {
+            Product::new(
+                &client,
+                serde_json::from_value(serde_json::json!({
+                    "type": "heating",
+                    "id": id,
+                    "created": 0,
+                    "props": { "zone": zone, "online": true, "working": true, "temperature": temperature },
+                    "state": { "target": 20.0, "mode": "SCHEDULE" }
+                }))
+                .expect("Product should deserialize"),
+            )
+        };
+
+        let zones = HeatingZone::group(vec![
+            heating("thermostat", Some("living-room"), 19.0),
+            heating("bedroom", None, 17.0),
+            Product::new(&client, ProductData::Unknown),
+            heating("valve", Some("living-room"), 20.0),
+        ]);
+
+        assert_eq!(
+            zones
+                .iter()
+                .map(|zone| zone.id.as_str())
+                .collect::<Vec<_>>(),
+            ["living-room", "bedroom"]
+        );
+        assert_eq!(zones[0].products.len(), 2);
+        assert_eq!(zones[0].current_temperature(), Some(19.5));
+        assert_eq!(zones[0].target_temperature(), Some(20.0));
+        assert!(matches!(zones[0].mode(), Some(Mode::Schedule)));
+    }
+
+    #[test]
+    fn test_status_deserialization() {
+        for (payload, expected) in [
+            (r#"{ "status": "ON" }"#, HeatingStatus::On),
+            (r#"{ "status": "OFF" }"#, HeatingStatus::Off),
+            (r#"{ "status": "PREHEAT" }"#, HeatingStatus::Preheat),
+            (
+                r#"{ "status": "DEFROST" }"#,
+                HeatingStatus::Unknown("DEFROST".to_string()),
+            ),
+        ] {
+            let states: States = serde_json::from_str(payload).expect("States should deserialize");
+
+            assert!(
+                matches!(states.first(), Some(State::Status(actual)) if *actual == expected),
+                "{payload} should deserialize to {expected:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_states_accessors() {
+        let states: States = serde_json::from_str(
+            r#"{ "target": 19.5, "mode": "BOOST", "boost": 30, "name": "Living Room", "status": "ON" }"#,
+        )
+        .expect("States should deserialize");
+
+        assert_eq!(states.target_temperature(), Some(19.5));
+        assert!(matches!(states.mode(), Some(Mode::Boost)));
+        assert_eq!(states.boost(), Some(Boost::Minutes(30)));
+        assert!(states.is_boosted());
+        assert_eq!(states.name(), Some("Living Room"));
+        assert_eq!(states.status(), Some(&HeatingStatus::On));
+        assert!(states.schedule().is_none());
+    }
+
+    #[test]
+    fn test_unknown_products_can_be_rejected() {
+        let raw = serde_json::json!({ "type": "warmwhitelight", "id": "1234" });
+
+        assert!(matches!(
+            ProductData::decode_known(raw),
+            Err(ApiError::UnknownProductType { raw }) if raw["id"] == "1234"
+        ));
+    }
+
+    #[test]
+    fn test_states_can_be_iterated_by_reference() {
+        let states = States(vec![
+            State::TargetTemperature(18.0),
+            State::OptimumStart(true),
+        ]);
+
+        let mut kinds = vec![];
+        for state in &states {
+            kinds.push(state.kind());
+        }
+
+        assert_eq!(kinds, ["target", "optimumStart"]);
+    }
+
+    #[test]
+    fn test_manual_target_is_sent_in_a_single_payload() {
+        let states = States::manual_target(19.5).expect("Target should be in range");
+
+        assert_eq!(
+            serde_json::to_value(&states).expect("States should serialize"),
+            serde_json::json!({ "mode": "MANUAL", "target": 19.5 })
+        );
+
+        assert!(matches!(
+            States::manual_target(40.0),
+            Err(ApiError::InvalidTargetTemperature(_))
+        ));
+    }
+
+    #[test]
+    fn test_states_match_by_kind() {
+        let state = State::TargetTemperature(18.0);
+
+        assert_eq!(state.kind(), "target");
+        assert!(state.matches_kind(&State::TargetTemperature(21.0)));
+        assert!(!state.matches_kind(&State::FrostProtection(18)));
+    }
+
+    #[test]
+    fn test_state_snapshot() {
+        let client = Client::new("Home Automation");
+
+        let product = Product::new(
+            &client,
+            serde_json::from_str(
+                r#"{
+                    "type": "hotwater",
+                    "id": "hot-water",
+                    "created": 0,
+                    "props": { "online": true, "working": false },
+                    "state": { "mode": "OFF", "status": "OFF" }
+                }"#,
+            )
+            .expect("Product should deserialize"),
+        );
+
+        assert_eq!(
+            product.state_snapshot(),
+            HashMap::from(
+                [
+                    ("mode", "Off"),
+                    ("status", "Off"),
+                    ("online", "true"),
+                    ("working", "false"),
+                ]
+                .map(|(key, value)| (key.to_string(), value.to_string()))
+            )
+        );
+        assert!(
+            Product::new(&client, ProductData::Unknown)
+                .state_snapshot()
+                .is_empty()
+        );
+    }
+
+    #[test]
+    fn test_scheduled_target_during_boost() {
+        let client = Client::new("Home Automation");
+
+        let product = heating(
+            &client,
+            serde_json::json!({ "online": true, "working": true, "previous": { "mode": "SCHEDULE", "target": 18.0 } }),
+            serde_json::json!({ "mode": "BOOST", "boost": 30, "target": 22.0 }),
+        );
+
+        assert_eq!(product.scheduled_target(), Some(18.0));
+    }
+
+    #[tokio::test]
+    async fn test_only_scheduled_heating_can_be_advanced() {
+        let client = Client::new("Home Automation");
+
+        let product = |r#type: &str, mode: &str| {
+            Product::new(
+                &client,
+                serde_json::from_value(serde_json::json!({
+                    "type": r#type,
+                    "id": r#type,
+                    "created": 0,
+                    "props": { "online": true, "working": true },
+                    "state": { "mode": mode }
+                }))
+                .expect("Product should deserialize"),
+            )
+        };
+
+        assert!(matches!(
+            product("hotwater", "SCHEDULE")
+                .advance(&chrono_tz::UTC)
+                .await,
+            Err(ApiError::UnsupportedProduct(_))
+        ));
+        assert!(matches!(
+            product("heating", "MANUAL").advance(&chrono_tz::UTC).await,
+            Err(ApiError::NotScheduled)
+        ));
+        assert!(matches!(
+            product("heating", "MANUAL")
+                .cancel_advance(&chrono_tz::UTC)
+                .await,
+            Err(ApiError::NotScheduled)
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_hot_water_can_only_be_turned_on_for_hot_water_products() {
+        let client = Client::new("Home Automation");
+
+        let mut product = heating(
+            &client,
+            serde_json::json!({ "online": true, "working": true }),
+            serde_json::json!({ "mode": "SCHEDULE", "target": 18.0 }),
+        );
+
+        assert!(matches!(
+            product.set_hot_water_on(true).await,
+            Err(ApiError::UnsupportedProduct(r#type)) if r#type == "heating"
+        ));
+    }
+
+    #[test]
+    fn test_product_data_display() {
+        let heating: ProductData = serde_json::from_str(
+            r#"{
+                "type": "heating",
+                "id": "1234",
+                "created": 0,
+                "props": { "online": true, "working": true, "temperature": 19.5 },
+                "state": { "name": "Living Room", "mode": "MANUAL" }
+            }"#,
+        )
+        .expect("Product should deserialize");
+
+        assert_eq!(
+            heating.to_string(),
+            "Heating \"Living Room\" (id=1234, 19.5°C, Manual)"
+        );
+        assert_eq!(ProductData::Unknown.to_string(), "Unknown product");
+    }
+
+    #[tokio::test]
+    async fn test_leak_sensors_are_read_only() {
+        let client = Client::new("Home Automation");
+
+        let mut leak_sensor = Product::new(
+            &client,
+            serde_json::from_str(
+                r#"{
+                    "type": "leaksensor",
+                    "id": "1234",
+                    "created": 0,
+                    "props": { "online": true, "leak": true, "battery": 90 },
+                    "state": { "name": "Kitchen" }
+                }"#,
+            )
+            .expect("Product should deserialize"),
+        );
+
+        let sensor = leak_sensor
+            .data
+            .as_leak_sensor()
+            .expect("Product should be a leak sensor");
+        assert!(sensor.leak_detected());
+        assert_eq!(sensor.battery_percentage(), Some(90));
+        assert_eq!(
+            leak_sensor.data.to_string(),
+            "Leak Sensor \"Kitchen\" (id=1234, leak detected)"
+        );
+
+        assert!(matches!(
+            leak_sensor
+                .set_state(&States(vec![State::Name("Utility".to_string())]))
+                .await,
+            Err(ApiError::UnsupportedProduct(r#type)) if r#type == "leaksensor"
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_offline_changes_can_be_rejected() {
+        let client = Client::new("Home Automation").reject_offline_changes(true);
+
+        let mut heating = heating(
+            &client,
+            serde_json::json!({ "online": false, "working": false }),
+            serde_json::json!({ "target": 18.0 }),
+        );
+
+        assert!(matches!(
+            heating.set_state(&States(vec![State::TargetTemperature(21.0)])).await,
+            Err(ApiError::DeviceOffline(id)) if id == "heating"
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_targets_cannot_be_set_beyond_the_longest_boost() {
+        let client = Client::new("Home Automation");
+
+        let mut heating = heating(
+            &client,
+            serde_json::json!({ "online": true, "working": true }),
+            serde_json::json!({ "target": 18.0 }),
+        );
+
+        for until in [
+            Utc::now() - chrono::Duration::minutes(1),
+            Utc::now() + chrono::Duration::hours(7),
+        ] {
+            assert!(matches!(
+                heating.set_target_until(21.0, until).await,
+                Err(ApiError::InvalidBoostEnd(end)) if end == until
+            ));
+        }
+    }
+
+    #[tokio::test]
+    async fn test_states_invalid_for_the_product_are_rejected() {
+        let client = Client::new("Home Automation");
+
+        let mut hot_water = Product::new(
+            &client,
+            serde_json::from_str(
+                r#"{
+                    "type": "hotwater",
+                    "id": "1234",
+                    "created": 0,
+                    "props": { "online": true, "working": true },
+                    "state": { "mode": "SCHEDULE" }
+                }"#,
+            )
+            .expect("Product should deserialize"),
+        );
+
+        assert!(hot_water.data.accepts_state(&State::Mode(Mode::Manual)));
+        assert!(matches!(
+            hot_water
+                .set_state(&States(vec![
+                    State::Mode(Mode::Manual),
+                    State::TargetTemperature(21.0),
+                ]))
+                .await,
+            Err(ApiError::InvalidStateForProduct { state, product_type })
+                if matches!(*state, State::TargetTemperature(_)) && product_type == "hotwater"
+        ));
+    }
+
+    #[test]
+    fn test_products_are_enabled_unless_reported_otherwise() {
+        for (enabled, expected) in [("", true), (r#", "enabled": false"#, false)] {
+            let data: ProductData = serde_json::from_str(&format!(
+                r#"{{
+                    "type": "hotwater",
+                    "id": "1234",
+                    "created": 0,
+                    "props": {{ "online": true, "working": true{enabled} }},
+                    "state": {{}}
+                }}"#
+            ))
+            .expect("Product should deserialize");
+
+            assert_eq!(data.is_enabled(), expected, "{enabled:?}");
+        }
+    }
+
+    #[tokio::test]
+    async fn test_states_set_by_id_are_checked_against_the_product_type() {
+        let client = Client::new("Home Automation");
+
+        assert_eq!(ProductType::HotWater.as_str(), "hotwater");
+        assert!(matches!(
+            client
+                .set_product_state_by_id(
+                    "1234",
+                    ProductType::HotWater,
+                    &States(vec![State::TargetTemperature(21.0)]),
+                )
+                .await,
+            Err(ApiError::InvalidStateForProduct { product_type, .. }) if product_type == "hotwater"
+        ));
+    }
+
+    #[test]
+    fn test_states_builder_validates_the_states_together() {
+        let states = States::builder()
+            .target_temperature(18.0)
+            .boost(BoostDuration::OneHour)
+            .target_temperature(21.0)
+            .build()
+            .expect("States should be valid");
+
+        assert!(matches!(states.mode(), Some(Mode::Boost)));
+        assert_eq!(states.boost(), Some(Boost::Minutes(60)));
+        assert_eq!(states.target_temperature(), Some(21.0));
+        assert_eq!(states.len(), 3);
+
+        assert!(matches!(
+            States::builder().target_temperature(50.0).build(),
+            Err(ApiError::InvalidTargetTemperature(_))
+        ));
+        assert!(matches!(
+            States::builder()
+                .mode(Mode::Manual)
+                .boost(BoostDuration::HalfHour)
+                .build(),
+            Err(ApiError::ConflictingStates(_))
+        ));
+        assert!(matches!(
+            States::builder()
+                .for_product(ProductType::HotWater)
+                .target_temperature(20.0)
+                .build(),
+            Err(ApiError::InvalidStateForProduct { .. })
+        ));
+    }
+
+    #[test]
+    fn test_product_types_are_parsed() {
+        for product_type in [ProductType::Heating, ProductType::HotWater] {
+            assert_eq!(
+                product_type.as_str().parse::<ProductType>().ok(),
+                Some(product_type)
+            );
+        }
+
+        assert!(matches!(
+            "leaksensor".parse::<ProductType>(),
+            Err(ApiError::UnsupportedProduct(r#type)) if r#type == "leaksensor"
+        ));
+    }
+
+    #[test]
+    fn test_supported_types_are_decoded() {
+        for r#type in ProductData::SUPPORTED_TYPES {
+            let data = ProductData::deserialize(&serde_json::json!({
+                "type": r#type,
+                "id": "1234",
+                "created": 0,
+                "props": { "online": true, "working": true },
+                "state": {}
+            }))
+            .expect("Product should deserialize");
+
+            assert_eq!(data.r#type(), *r#type);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_device_reported_target_bounds_are_preferred() {
+        let client = Client::new("Home Automation");
+
+        let mut bounded = heating(
+            &client,
+            serde_json::json!({ "online": true, "working": true, "minHeat": 7, "maxHeat": 25 }),
+            serde_json::json!({ "target": 18.0 }),
+        );
+
+        assert_eq!(
+            bounded
+                .data
+                .as_heating()
+                .and_then(Heating::target_temperature_bounds),
+            Some((7.0, 25.0))
+        );
+        assert!(matches!(
+            bounded.set_manual_target(28.0).await,
+            Err(ApiError::InvalidTargetTemperature(_))
+        ));
+        assert!(matches!(
+            bounded
+                .set_state(&States(vec![State::TargetTemperature(28.0)]))
+                .await,
+            Err(ApiError::InvalidTargetTemperature(_))
+        ));
+
+        let mut zones = HeatingZone::group(vec![bounded]);
+
+        assert_eq!(
+            zones[0]
+                .set_target_temperature(28.0)
+                .await
+                .expect("Zone target should be attempted"),
+            [false]
+        );
+        assert!(matches!(
+            zones[0]
+                .set_target_temperature(MAX_TARGET_TEMPERATURE + 1.0)
+                .await,
+            Err(ApiError::InvalidTargetTemperature(_))
+        ));
+
+        let unbounded = heating(
+            &client,
+            serde_json::json!({ "online": true, "working": true }),
+            serde_json::json!({ "target": 18.0 }),
+        );
+
+        assert!(unbounded.ensure_valid_target(28.0).is_ok());
+        assert!(
+            unbounded
+                .ensure_valid_target(MAX_TARGET_TEMPERATURE + 1.0)
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn test_holiday_mode() {
+        for (holiday_mode, expected) in [
+            ("", false),
+            (r#", "holidayMode": true"#, true),
+            (
+                r#", "holidayMode": { "enabled": true, "temperature": 7 }"#,
+                true,
+            ),
+            (r#", "holidayMode": { "enabled": false }"#, false),
+        ] {
+            let heating: Heating = serde_json::from_str(&format!(
+                r#"{{
+                    "id": "1234",
+                    "created": 0,
+                    "props": {{ "online": true, "working": true{holiday_mode} }},
+                    "state": {{}}
+                }}"#
+            ))
+            .expect("Heating should deserialize");
+
+            assert_eq!(heating.is_in_holiday_mode(), expected, "{holiday_mode:?}");
+        }
+    }
+
+    #[test]
+    fn test_hot_water_temperature() {
+        for (temperature, expected) in [
+            ("", None),
+            (r#", "temperature": 48.5"#, Some(48.5)),
+            (r#", "waterTemperature": 52"#, Some(52.0)),
+            (r#", "cylinderTemperature": 45.0"#, Some(45.0)),
+        ] {
+            let hot_water: HotWater = serde_json::from_str(&format!(
+                r#"{{
+                    "id": "1234",
+                    "created": 0,
+                    "props": {{ "online": true, "working": true{temperature} }},
+                    "state": {{}}
+                }}"#
+            ))
+            .expect("Hot Water should deserialize");
+
+            assert_eq!(hot_water.water_temperature(), expected, "{temperature:?}");
+        }
+    }
+
+    #[test]
+    fn test_concrete_products_can_be_extracted() {
+        let heating: ProductData = serde_json::from_str(
+            r#"{
+                "type": "heating",
+                "id": "1234",
+                "created": 0,
+                "props": { "online": true, "working": true },
+                "state": { "mode": "MANUAL" }
+            }"#,
+        )
+        .expect("Product should deserialize");
+
+        assert_eq!(
+            heating.as_heating().map(|heating| heating.id.as_str()),
+            Some("1234")
+        );
+        assert!(heating.as_hot_water().is_none());
+        assert!(matches!(
+            HotWater::try_from(heating),
+            Err(ApiError::UnsupportedProduct(r#type)) if r#type == "heating"
+        ));
+        assert!(Heating::try_from(ProductData::Unknown).is_err());
+    }
+
+    #[test]
+    fn test_previous_states_are_captured_before_a_change() {
+        let client = Client::new("Home Automation");
+
+        let product = heating(
+            &client,
+            serde_json::json!({ "online": true, "working": true }),
+            serde_json::json!({ "mode": "SCHEDULE", "target": 18.0, "name": "Living Room" }),
+        );
+
+        let previous_states = product.previous_states(&States(vec![
+            State::Mode(Mode::Manual),
+            State::TargetTemperature(21.0),
+            State::Boost(Some(Boost::Minutes(30))),
+        ]));
+
+        assert!(matches!(previous_states.mode(), Some(Mode::Schedule)));
+        assert_eq!(previous_states.target_temperature(), Some(18.0));
+        assert_eq!(previous_states.len(), 2);
     }
 }
@@ -0,0 +1,67 @@
+use crate::client::api::HiveApi;
+use crate::client::api::error::ApiError;
+#[cfg(feature = "fixtures")]
+use crate::client::api::fixtures::RecordedCall;
+use crate::client::authentication::Tokens;
+use crate::helper::url::{Url, get_base_url};
+use serde_json::Value;
+
+impl HiveApi {
+    pub(crate) async fn get_raw(&self, tokens: &Tokens, path: &str) -> Result<Value, ApiError> {
+        #[cfg(feature = "fixtures")]
+        if let Some(fixtures) = &self.fixtures {
+            return fixtures.load(&format!("raw/{}", path.trim_start_matches('/')));
+        }
+
+        let response = self
+            .client
+            .get(get_base_url(&Url::Raw { path }))
+            .header("Authorization", &tokens.id_token)
+            .send()
+            .await?;
+
+        self.decode_json(response).await
+    }
+
+    pub(crate) async fn post_raw(
+        &self,
+        tokens: &Tokens,
+        path: &str,
+        body: &Value,
+    ) -> Result<Value, ApiError> {
+        #[cfg(feature = "fixtures")]
+        if let Some(fixtures) = &self.fixtures {
+            fixtures.record(RecordedCall::Post {
+                path: path.to_string(),
+                body: body.clone(),
+            });
+
+            return Ok(Value::Null);
+        }
+
+        let response = self
+            .client
+            .post(get_base_url(&Url::Raw { path }))
+            .body(serde_json::to_string(body)?)
+            .header("Authorization", &tokens.id_token)
+            .send()
+            .await?;
+
+        self.decode_json(response).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::helper::url::{BEEKEEPER_BASE_URL, Url, get_base_url};
+
+    #[test]
+    fn test_raw_paths_are_prefixed_with_the_base_url() {
+        for path in ["nodes/heating", "/nodes/heating"] {
+            assert_eq!(
+                get_base_url(&Url::Raw { path }),
+                format!("{BEEKEEPER_BASE_URL}/nodes/heating")
+            );
+        }
+    }
+}
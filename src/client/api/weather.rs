@@ -1,28 +1,91 @@
 use crate::client::api::{ApiError, HiveApi};
 use crate::client::authentication::Tokens;
+use crate::helper::temperature::{celsius_to_fahrenheit, fahrenheit_to_celsius};
+use crate::helper::timestamp;
 use crate::helper::url::{Url, get_base_url};
+use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use std::fmt;
 use std::fmt::Debug;
 
-#[derive(Serialize, Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug, Clone)]
 #[serde(tag = "unit")]
+#[non_exhaustive]
 /// The current weather temperature.
 pub enum Temperature {
     #[serde(rename = "C")]
     #[allow(missing_docs)]
     Celsius { value: f32 },
+
+    #[serde(rename = "F")]
+    #[allow(missing_docs)]
+    Fahrenheit { value: f32 },
+}
+
+impl Temperature {
+    /// The temperature, in Celsius.
+    #[must_use]
+    pub fn celsius(&self) -> f32 {
+        match self {
+            Self::Celsius { value } => *value,
+            Self::Fahrenheit { value } => fahrenheit_to_celsius(*value),
+        }
+    }
+
+    /// The temperature, in Fahrenheit.
+    #[must_use]
+    pub fn fahrenheit(&self) -> f32 {
+        match self {
+            Self::Celsius { value } => celsius_to_fahrenheit(*value),
+            Self::Fahrenheit { value } => *value,
+        }
+    }
 }
 
 impl fmt::Display for Temperature {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
             Self::Celsius { value } => write!(f, "{value}°C"),
+            Self::Fahrenheit { value } => write!(f, "{value}°F"),
         }
     }
 }
 
-#[derive(Serialize, Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug, Clone, Copy)]
+/// The units which temperatures are returned in by the Hive Weather API.
+pub enum TemperatureUnit {
+    #[serde(rename = "C")]
+    #[allow(missing_docs)]
+    Celsius,
+
+    #[serde(rename = "F")]
+    #[allow(missing_docs)]
+    Fahrenheit,
+}
+
+impl TemperatureUnit {
+    const fn as_query_value(self) -> &'static str {
+        match self {
+            Self::Celsius => "C",
+            Self::Fahrenheit => "F",
+        }
+    }
+}
+
+#[derive(Debug, Default, Clone)]
+/// Options which change the format of the weather returned by the Hive Weather API.
+///
+/// Any option which is not set is left to the Hive Weather API's default (Celsius, in
+/// British English).
+pub struct WeatherOptions {
+    /// The units to return the temperature in.
+    pub units: Option<TemperatureUnit>,
+
+    /// The language to return the weather description in (i.e. "en-GB", "fr-FR").
+    pub language: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
 #[allow(missing_docs)]
 pub struct WeatherData {
     /// An enumeration of different whether types (i.e. "`clear_sky`").
@@ -32,32 +95,160 @@ pub struct WeatherData {
     /// The current temperature.
     pub temperature: Temperature,
 
+    #[serde(default, rename = "feelsLike", alias = "feels_like")]
+    /// The apparent ("feels like") temperature, if provided by Hive.
+    pub feels_like: Option<Temperature>,
+
     /// A human readable description of the weather (i.e. "clear sky").
     pub description: String,
+
+    #[serde(default, rename = "timestamp", with = "timestamp::option")]
+    /// The date and time the weather was observed by Hive's weather provider, if provided.
+    pub observed_at: Option<DateTime<Utc>>,
 }
 
 /// Weather information returned from Hive.
-#[derive(Serialize, Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct Weather {
     #[allow(missing_docs)]
     #[serde(rename = "weather")]
     pub data: WeatherData,
 }
 
+/// Weather information which may have been served from the last successful fetch, rather than
+/// freshly from Hive.
+#[derive(Debug, Clone)]
+pub struct CachedWeather {
+    /// The weather information.
+    pub weather: Weather,
+
+    /// Whether the weather information is the last known value, because a fresh fetch from Hive
+    /// failed.
+    pub is_stale: bool,
+}
+
+impl Weather {
+    /// Decode the weather returned by Hive for a postcode, where Hive returns an empty
+    /// response if its weather provider has no weather for the postcode.
+    fn decode(response: Option<serde_json::Value>, postcode: &str) -> Result<Self, ApiError> {
+        let has_weather = response
+            .as_ref()
+            .and_then(|response| response.get("weather"))
+            .is_some_and(|weather| {
+                weather
+                    .as_object()
+                    .is_some_and(|weather| !weather.is_empty())
+            });
+
+        match response {
+            Some(response) if has_weather => Ok(serde_json::from_value(response)?),
+            _ => Err(ApiError::WeatherUnavailable {
+                postcode: postcode.to_string(),
+            }),
+        }
+    }
+}
+
 impl HiveApi {
     pub(crate) async fn get_weather(
         &self,
         tokens: &Tokens,
         postcode: &str,
+        options: &WeatherOptions,
     ) -> Result<Weather, ApiError> {
+        #[cfg(feature = "fixtures")]
+        if let Some(fixtures) = &self.fixtures {
+            return Weather::decode(Some(fixtures.load("weather")?), postcode);
+        }
+
+        let mut query = vec![("postcode", postcode.replace(' ', ""))];
+
+        if let Some(units) = options.units {
+            query.push(("unit", units.as_query_value().to_string()));
+        }
+
+        if let Some(language) = &options.language {
+            query.push(("locale", language.clone()));
+        }
+
         let response = self
             .client
             .get(get_base_url(&Url::Weather))
-            .query(&[("postcode", postcode.replace(' ', ""))])
+            .query(&query)
             .header("Authorization", &tokens.id_token)
             .send()
             .await;
 
-        Ok(response?.json::<Weather>().await?)
+        match self.decode_json(response?).await {
+            Err(ApiError::InvalidResponse(error)) if error.is_eof() => {
+                Weather::decode(None, postcode)
+            }
+            response => Weather::decode(response?, postcode),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_feels_like_temperature_is_optional() {
+        let weather: Weather = serde_json::from_str(
+            r#"{ "weather": { "icon": "clear_sky", "description": "clear sky", "temperature": { "unit": "C", "value": 12.5 } } }"#,
+        )
+        .expect("Weather should deserialize");
+
+        assert!(weather.data.feels_like.is_none());
+        assert!(weather.data.observed_at.is_none());
+
+        let weather: Weather = serde_json::from_str(
+            r#"{ "weather": { "icon": "clear_sky", "description": "clear sky", "temperature": { "unit": "C", "value": 12.5 }, "feelsLike": { "unit": "C", "value": 9.0 } } }"#,
+        )
+        .expect("Weather should deserialize");
+
+        assert!(matches!(
+            weather.data.feels_like,
+            Some(Temperature::Celsius { value }) if (value - 9.0).abs() < f32::EPSILON
+        ));
+    }
+
+    #[test]
+    fn test_weather_without_coverage_is_unavailable() {
+        for response in [
+            None,
+            Some(serde_json::json!({})),
+            Some(serde_json::json!({ "weather": {} })),
+        ] {
+            assert!(matches!(
+                Weather::decode(response, "SW1A 1AA"),
+                Err(ApiError::WeatherUnavailable { postcode }) if postcode == "SW1A 1AA"
+            ));
+        }
+
+        assert!(
+            Weather::decode(
+                Some(serde_json::json!({ "weather": {
+                    "icon": "clear_sky",
+                    "description": "clear sky",
+                    "temperature": { "unit": "C", "value": 12.5 }
+                } })),
+                "SW1A 1AA"
+            )
+            .is_ok()
+        );
+    }
+
+    #[test]
+    fn test_observation_time() {
+        let weather: Weather = serde_json::from_str(
+            r#"{ "weather": { "icon": "clear_sky", "description": "clear sky", "temperature": { "unit": "C", "value": 12.5 }, "timestamp": 1748844720000 } }"#,
+        )
+        .expect("Weather should deserialize");
+
+        assert_eq!(
+            weather.data.observed_at,
+            DateTime::from_timestamp_millis(1_748_844_720_000)
+        );
     }
 }
@@ -0,0 +1,118 @@
+use crate::client::authentication::HiveAuth;
+use crate::helper::target;
+use aws_sdk_cognitoidentityprovider::error::SdkError;
+use aws_sdk_cognitoidentityprovider::operation::initiate_auth::builders::InitiateAuthFluentBuilder;
+use aws_sdk_cognitoidentityprovider::operation::initiate_auth::{
+    InitiateAuthError, InitiateAuthOutput,
+};
+use std::time::Duration;
+
+/// The default for the longest a [`crate::Client`] will spend backing off from Cognito's rate
+/// limits, before giving up on a login or refresh. See [`crate::Client::with_max_throttle_wait`].
+pub const DEFAULT_MAX_THROTTLE_WAIT: Duration = Duration::from_secs(30);
+
+/// The delay before the first retry of a throttled request, which doubles with each retry.
+const INITIAL_BACKOFF: Duration = Duration::from_millis(500);
+
+/// An exponential backoff, limited to a total amount of time spent waiting.
+#[derive(Debug)]
+struct Backoff {
+    next: Duration,
+    remaining: Duration,
+}
+
+impl Backoff {
+    const fn new(max_wait: Duration) -> Self {
+        Self {
+            next: INITIAL_BACKOFF,
+            remaining: max_wait,
+        }
+    }
+
+    /// The delay before the next retry, preferring the delay requested by Cognito (if any).
+    ///
+    /// Returns `None` if waiting would exceed the time left in the backoff.
+    fn next_delay(&mut self, retry_after: Option<Duration>) -> Option<Duration> {
+        let delay = retry_after.unwrap_or(self.next);
+
+        self.remaining = self.remaining.checked_sub(delay)?;
+        self.next = self.next.saturating_mul(2);
+
+        Some(delay)
+    }
+}
+
+impl HiveAuth {
+    /// Send a request to Cognito to initiate authentication (either a login, or a refresh),
+    /// transparently retrying while Cognito is throttling requests.
+    ///
+    /// Throttled requests are retried with an exponential backoff (or after the delay
+    /// requested by Cognito), until the configured maximum wait is reached.
+    pub async fn initiate_auth_with_backoff(
+        &self,
+        builder: InitiateAuthFluentBuilder,
+    ) -> Result<InitiateAuthOutput, SdkError<InitiateAuthError>> {
+        let mut backoff = Backoff::new(self.config.max_throttle_wait);
+
+        loop {
+            let err = match builder.clone().send().await {
+                Ok(output) => return Ok(output),
+                Err(err) => err,
+            };
+
+            let throttled = err
+                .as_service_error()
+                .is_some_and(InitiateAuthError::is_too_many_requests_exception);
+
+            let Some(delay) = throttled
+                .then(|| backoff.next_delay(retry_after(&err)))
+                .flatten()
+            else {
+                return Err(err);
+            };
+
+            log::warn!(target: target::AUTH, "Cognito is throttling requests, retrying in {delay:?}.");
+
+            tokio::time::sleep(delay).await;
+        }
+    }
+}
+
+/// The delay requested by Cognito in the `Retry-After` header of a response (if any).
+fn retry_after(err: &SdkError<InitiateAuthError>) -> Option<Duration> {
+    err.raw_response()?
+        .headers()
+        .get("retry-after")?
+        .trim()
+        .parse()
+        .ok()
+        .map(Duration::from_secs)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_backoff_doubles_until_the_maximum_wait() {
+        let mut backoff = Backoff::new(Duration::from_secs(4));
+
+        assert_eq!(backoff.next_delay(None), Some(Duration::from_millis(500)));
+        assert_eq!(backoff.next_delay(None), Some(Duration::from_secs(1)));
+        assert_eq!(backoff.next_delay(None), Some(Duration::from_secs(2)));
+
+        // Only 500ms of the maximum wait remains, which isn't enough for the next retry.
+        assert_eq!(backoff.next_delay(None), None);
+    }
+
+    #[test]
+    fn test_backoff_prefers_the_delay_requested_by_cognito() {
+        let mut backoff = Backoff::new(Duration::from_secs(4));
+
+        assert_eq!(
+            backoff.next_delay(Some(Duration::from_secs(3))),
+            Some(Duration::from_secs(3))
+        );
+        assert_eq!(backoff.next_delay(Some(Duration::from_secs(3))), None);
+    }
+}
@@ -0,0 +1,35 @@
+use crate::AuthenticationError;
+use crate::authentication::LoginSession;
+use aws_cognito_srp::SrpClient;
+use aws_sdk_cognitoidentityprovider::operation::respond_to_auth_challenge::RespondToAuthChallengeOutput;
+use aws_sdk_cognitoidentityprovider::types::ChallengeNameType;
+use std::collections::HashMap;
+
+pub async fn handle_challenge(
+    cognito_client: &aws_sdk_cognitoidentityprovider::Client,
+    client_id: &str,
+    device_srp_client: Option<&SrpClient<aws_cognito_srp::TrackedDevice>>,
+    session: &LoginSession,
+    mut responses: HashMap<String, String>,
+) -> Result<RespondToAuthChallengeOutput, AuthenticationError> {
+    responses
+        .entry("USERNAME".to_string())
+        .or_insert_with(|| session.0.clone());
+
+    if let Some(device_key) = device_srp_client
+        .map(|device_srp_client| device_srp_client.get_auth_parameters().device_key)
+    {
+        responses
+            .entry("DEVICE_KEY".to_string())
+            .or_insert(device_key);
+    }
+
+    Ok(cognito_client
+        .respond_to_auth_challenge()
+        .set_challenge_responses(Some(responses))
+        .set_session(Option::clone(&session.1))
+        .client_id(client_id)
+        .challenge_name(ChallengeNameType::CustomChallenge)
+        .send()
+        .await?)
+}
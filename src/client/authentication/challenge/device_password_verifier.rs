@@ -1,6 +1,6 @@
 use crate::AuthenticationError;
 use crate::authentication::LoginSession;
-use crate::constants::CLIENT_ID;
+use crate::client::authentication::challenge::parameters::ChallengeParameters;
 use aws_cognito_srp::{SrpClient, VerificationParameters};
 use aws_sdk_cognitoidentityprovider::operation::respond_to_auth_challenge::RespondToAuthChallengeOutput;
 use aws_sdk_cognitoidentityprovider::types::ChallengeNameType;
@@ -8,19 +8,17 @@ use std::collections::HashMap;
 
 pub async fn handle_challenge(
     cognito_client: &aws_sdk_cognitoidentityprovider::Client,
+    client_id: &str,
     device_srp_client: &SrpClient<aws_cognito_srp::TrackedDevice>,
     session: &LoginSession,
     parameters: HashMap<String, String>,
 ) -> Result<RespondToAuthChallengeOutput, AuthenticationError> {
-    let srp_b = parameters
-        .get("SRP_B")
-        .ok_or_else(|| AuthenticationError::MissingChallengeParameter("SRP_B".to_string()))?;
-    let salt = parameters
-        .get("SALT")
-        .ok_or_else(|| AuthenticationError::MissingChallengeParameter("SALT".to_string()))?;
-    let secret_block = parameters.get("SECRET_BLOCK").ok_or_else(|| {
-        AuthenticationError::MissingChallengeParameter("SECRET_BLOCK".to_string())
-    })?;
+    let ChallengeParameters {
+        srp_b,
+        salt,
+        secret_block,
+    } = ChallengeParameters::parse(&parameters)
+        .map_err(AuthenticationError::MissingChallengeParameter)?;
 
     let VerificationParameters {
         password_claim_secret_block,
@@ -34,7 +32,7 @@ pub async fn handle_challenge(
         .respond_to_auth_challenge()
         .challenge_name(ChallengeNameType::DevicePasswordVerifier)
         .set_session(session.1.clone())
-        .client_id(CLIENT_ID)
+        .client_id(client_id)
         .challenge_responses("USERNAME", session.0.clone())
         .challenge_responses("PASSWORD_CLAIM_SECRET_BLOCK", password_claim_secret_block)
         .challenge_responses("PASSWORD_CLAIM_SIGNATURE", password_claim_signature)
@@ -1,19 +1,24 @@
 use crate::AuthenticationError;
 use crate::authentication::user::UntrustedDevice;
 use crate::client::authentication::{HiveAuth, Tokens};
+use crate::helper::redact::Redacted;
+use crate::helper::target;
 use aws_sdk_cognitoidentityprovider::operation::respond_to_auth_challenge::RespondToAuthChallengeOutput;
 use aws_sdk_cognitoidentityprovider::types::{
     AuthenticationResultType, ChallengeNameType, NewDeviceMetadataType,
 };
 use std::collections::HashMap;
-use std::fmt::Debug;
+use std::fmt;
+use std::fmt::{Debug, Formatter};
 
+mod custom;
 mod device_password_verifier;
 mod device_srp_auth;
+mod parameters;
 mod password_verifier;
 mod sms_mfa;
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 #[non_exhaustive]
 /// The Hive authentication servers have requested a challenge be responded to before
 /// the authentication can be completed.
@@ -23,7 +28,13 @@ pub enum ChallengeRequest {
     ///
     /// These codes are sent to the phone number associated with the user account, and will
     /// be six digits long.
-    SmsMfa,
+    SmsMfa {
+        /// The number of attempts remaining to enter the code, where Hive reports it.
+        ///
+        /// Once no attempts remain, responding to the challenge fails with
+        /// [`AuthenticationError::MfaAttemptsExhausted`].
+        attempts_remaining: Option<u8>,
+    },
 
     /// The authentication flow has requested a password verifier challenge.
     ///
@@ -31,18 +42,38 @@ pub enum ChallengeRequest {
     #[doc(hidden)]
     PasswordVerifier,
 
+    /// The authentication flow has requested a custom challenge, which is defined by Hive
+    /// rather than Cognito.
+    ///
+    /// The crate has no knowledge of how to answer custom challenges, so this acts as an escape
+    /// hatch for handling new or bespoke challenges. Answer it with
+    /// [`ChallengeResponse::Custom`].
+    Custom {
+        /// The name of the challenge.
+        name: String,
+
+        /// The parameters issued alongside the challenge by the Hive authentication servers.
+        parameters: HashMap<String, String>,
+    },
+
     /// The authentication flow has requested an unexpected challenge which cannot be handled by
     /// the crate.
     Unsupported(String),
 }
 
-#[derive(Debug)]
 #[non_exhaustive]
 /// A response to a [`ChallengeRequest`] issued by the Hive authentication servers.
 pub enum ChallengeResponse {
     /// A response to the [`ChallengeRequest::SmsMfa`] challenge, with the SMS code delivered to
     /// the user's phone.
     SmsMfa(String),
+
+    /// A response to the [`ChallengeRequest::Custom`] challenge, with the challenge responses
+    /// to pass through to the Hive authentication servers as-is.
+    ///
+    /// The `USERNAME` (and, where a trusted device is in use, the `DEVICE_KEY`) will be added
+    /// to the responses automatically, unless they have already been provided.
+    Custom(HashMap<String, String>),
     #[doc(hidden)]
     PasswordVerifier(HashMap<String, String>),
     #[doc(hidden)]
@@ -51,6 +82,55 @@ pub enum ChallengeResponse {
     DevicePasswordVerifier(HashMap<String, String>),
 }
 
+impl Debug for ChallengeResponse {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::SmsMfa(code) => f.debug_tuple("SmsMfa").field(&Redacted(code)).finish(),
+            Self::Custom(responses) => f.debug_tuple("Custom").field(&responses.keys()).finish(),
+            Self::PasswordVerifier(parameters) => f
+                .debug_tuple("PasswordVerifier")
+                .field(&parameters.keys())
+                .finish(),
+            Self::DeviceSrpAuth => f.write_str("DeviceSrpAuth"),
+            Self::DevicePasswordVerifier(parameters) => f
+                .debug_tuple("DevicePasswordVerifier")
+                .field(&parameters.keys())
+                .finish(),
+        }
+    }
+}
+
+impl ChallengeRequest {
+    /// The challenge parameter which Hive reports the number of attempts remaining in.
+    const ATTEMPTS_REMAINING_PARAMETER: &str = "ATTEMPTS_REMAINING";
+
+    pub(crate) fn sms_mfa(parameters: Option<&HashMap<String, String>>) -> Self {
+        Self::SmsMfa {
+            attempts_remaining: parameters
+                .and_then(|parameters| parameters.get(Self::ATTEMPTS_REMAINING_PARAMETER))
+                .and_then(|attempts_remaining| attempts_remaining.trim().parse().ok()),
+        }
+    }
+
+    pub(crate) fn custom(parameters: Option<HashMap<String, String>>) -> Self {
+        Self::Custom {
+            name: ChallengeNameType::CustomChallenge.to_string(),
+            parameters: parameters.unwrap_or_default(),
+        }
+    }
+}
+
+impl ChallengeResponse {
+    /// Check the response is well-formed, so that obviously invalid responses don't use up
+    /// one of the user's limited challenge attempts.
+    pub(crate) fn is_well_formed(&self) -> bool {
+        match self {
+            Self::SmsMfa(code) => code.len() == 6 && code.chars().all(|c| c.is_ascii_digit()),
+            _ => true,
+        }
+    }
+}
+
 impl HiveAuth {
     pub(crate) async fn respond_to_challenge(
         &self,
@@ -62,7 +142,7 @@ impl HiveAuth {
                 .as_mut()
                 .ok_or(AuthenticationError::NoAuthenticationInProgress)?;
 
-            log::info!(
+            log::info!(target: target::AUTH,
                 "Responding to challenge with response: {:?}",
                 &challenge_response
             );
@@ -71,7 +151,10 @@ impl HiveAuth {
                 ChallengeResponse::PasswordVerifier(parameters) => {
                     password_verifier::respond_to_challenge(
                         &self.cognito,
-                        &self.user_srp_client,
+                        &self.config.client_id,
+                        self.user_srp_client
+                            .as_ref()
+                            .ok_or(AuthenticationError::NoAuthenticationInProgress)?,
                         self.device_srp_client.as_ref(),
                         session,
                         parameters,
@@ -81,6 +164,7 @@ impl HiveAuth {
                 ChallengeResponse::DeviceSrpAuth => {
                     device_srp_auth::handle_challenge(
                         &self.cognito,
+                        &self.config.client_id,
                         self.device_srp_client
                             .as_ref()
                             .ok_or(AuthenticationError::NoAuthenticationInProgress)?,
@@ -91,6 +175,7 @@ impl HiveAuth {
                 ChallengeResponse::DevicePasswordVerifier(parameters) => {
                     device_password_verifier::handle_challenge(
                         &self.cognito,
+                        &self.config.client_id,
                         self.device_srp_client
                             .as_ref()
                             .ok_or(AuthenticationError::NoAuthenticationInProgress)?,
@@ -99,9 +184,20 @@ impl HiveAuth {
                     )
                     .await?
                 }
+                ChallengeResponse::Custom(responses) => {
+                    custom::handle_challenge(
+                        &self.cognito,
+                        &self.config.client_id,
+                        self.device_srp_client.as_ref(),
+                        session,
+                        responses,
+                    )
+                    .await?
+                }
                 ChallengeResponse::SmsMfa(code) => {
                     sms_mfa::handle_challenge(
                         &self.cognito,
+                        &self.config.client_id,
                         self.device_srp_client.as_ref(),
                         session,
                         &code,
@@ -147,7 +243,13 @@ impl HiveAuth {
                     }
 
                     Ok((
-                        Tokens::new(id_token, access_token, refresh_token, expires_in),
+                        Tokens::new(
+                            id_token,
+                            access_token,
+                            refresh_token,
+                            expires_in,
+                            &*self.clock,
+                        ),
                         untrusted_device,
                     ))
                 } else {
@@ -166,9 +268,77 @@ impl HiveAuth {
                 .await
             }
             Some(ChallengeNameType::SmsMfa) => {
-                Err(AuthenticationError::NextChallenge(ChallengeRequest::SmsMfa))
+                let challenge = ChallengeRequest::sms_mfa(response.challenge_parameters.as_ref());
+
+                match challenge {
+                    ChallengeRequest::SmsMfa {
+                        attempts_remaining: Some(0),
+                    } => Err(AuthenticationError::MfaAttemptsExhausted),
+                    challenge => Err(AuthenticationError::NextChallenge(challenge)),
+                }
             }
+            Some(ChallengeNameType::CustomChallenge) => Err(AuthenticationError::NextChallenge(
+                ChallengeRequest::custom(response.challenge_parameters),
+            )),
             Some(name) => Err(AuthenticationError::UnsupportedChallenge(name.to_string())),
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_custom_challenges_carry_their_parameters() {
+        let request = ChallengeRequest::custom(Some(HashMap::from([(
+            "question".to_string(),
+            "What is your favourite colour?".to_string(),
+        )])));
+
+        let ChallengeRequest::Custom { name, parameters } = request else {
+            panic!("Expected a custom challenge")
+        };
+
+        assert_eq!(name, "CUSTOM_CHALLENGE");
+        assert_eq!(parameters["question"], "What is your favourite colour?");
+    }
+
+    #[test]
+    fn test_sms_mfa_challenges_carry_the_attempts_remaining() {
+        let parameters = HashMap::from([("ATTEMPTS_REMAINING".to_string(), "1".to_string())]);
+
+        assert!(matches!(
+            ChallengeRequest::sms_mfa(Some(&parameters)),
+            ChallengeRequest::SmsMfa {
+                attempts_remaining: Some(1)
+            }
+        ));
+        assert!(matches!(
+            ChallengeRequest::sms_mfa(None),
+            ChallengeRequest::SmsMfa {
+                attempts_remaining: None
+            }
+        ));
+    }
+
+    #[test]
+    fn test_custom_challenge_responses_are_redacted() {
+        let response =
+            ChallengeResponse::Custom(HashMap::from([("ANSWER".to_string(), "blue".to_string())]));
+
+        assert_eq!(format!("{response:?}"), r#"Custom(["ANSWER"])"#);
+    }
+
+    #[test]
+    fn test_sms_mfa_code_must_be_six_digits() {
+        assert!(ChallengeResponse::SmsMfa("123456".to_string()).is_well_formed());
+
+        for code in ["", "12345", "1234567", "12345a", " 123456", "１２３４５６"] {
+            assert!(
+                !ChallengeResponse::SmsMfa(code.to_string()).is_well_formed(),
+                "{code:?} should be rejected"
+            );
+        }
+    }
+}
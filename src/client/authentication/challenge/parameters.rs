@@ -0,0 +1,105 @@
+#[cfg(doc)]
+use crate::AuthenticationError;
+use std::collections::HashMap;
+
+const SRP_B: &str = "SRP_B";
+const SALT: &str = "SALT";
+const SECRET_BLOCK: &str = "SECRET_BLOCK";
+const USER_ID_FOR_SRP: &str = "USER_ID_FOR_SRP";
+
+/// The parameters issued alongside a password verifier challenge (for either the user, or a
+/// trusted device), which are needed to complete the SRP exchange.
+#[derive(Debug)]
+pub struct ChallengeParameters<'a> {
+    pub srp_b: &'a str,
+    pub salt: &'a str,
+    pub secret_block: &'a str,
+}
+
+impl<'a> ChallengeParameters<'a> {
+    /// Parse the parameters of a device password verifier challenge.
+    ///
+    /// # Errors
+    ///
+    /// Returns the names of every required parameter which is missing, separated by commas
+    /// (as reported by [`AuthenticationError::MissingChallengeParameter`]).
+    pub fn parse(parameters: &'a HashMap<String, String>) -> Result<Self, String> {
+        let [srp_b, salt, secret_block] = required(parameters, [SRP_B, SALT, SECRET_BLOCK])?;
+
+        Ok(Self {
+            srp_b,
+            salt,
+            secret_block,
+        })
+    }
+
+    /// Parse the parameters of a user's password verifier challenge, which also include the
+    /// ID of the user to use for the SRP exchange.
+    ///
+    /// # Errors
+    ///
+    /// Returns the names of every required parameter which is missing, separated by commas
+    /// (as reported by [`AuthenticationError::MissingChallengeParameter`]).
+    pub fn parse_with_user_id(
+        parameters: &'a HashMap<String, String>,
+    ) -> Result<(Self, &'a str), String> {
+        let [srp_b, salt, secret_block, user_id] =
+            required(parameters, [SRP_B, SALT, SECRET_BLOCK, USER_ID_FOR_SRP])?;
+
+        Ok((
+            Self {
+                srp_b,
+                salt,
+                secret_block,
+            },
+            user_id,
+        ))
+    }
+}
+
+/// Take each of the required keys from the parameters, or fail listing all of those missing.
+fn required<'a, const N: usize>(
+    parameters: &'a HashMap<String, String>,
+    keys: [&str; N],
+) -> Result<[&'a str; N], String> {
+    let missing: Vec<&str> = keys
+        .into_iter()
+        .filter(|key| !parameters.contains_key(*key))
+        .collect();
+
+    if !missing.is_empty() {
+        return Err(missing.join(", "));
+    }
+
+    Ok(keys.map(|key| parameters[key].as_str()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_every_missing_parameter_is_reported() {
+        let parameters = HashMap::from([("SALT".to_string(), "salt".to_string())]);
+
+        assert_eq!(
+            ChallengeParameters::parse_with_user_id(&parameters).err(),
+            Some("SRP_B, SECRET_BLOCK, USER_ID_FOR_SRP".to_string())
+        );
+    }
+
+    #[test]
+    fn test_parameters_are_parsed() {
+        let parameters = HashMap::from([
+            ("SRP_B".to_string(), "srp_b".to_string()),
+            ("SALT".to_string(), "salt".to_string()),
+            ("SECRET_BLOCK".to_string(), "secret_block".to_string()),
+        ]);
+
+        let parsed = ChallengeParameters::parse(&parameters).expect("Parameters should parse");
+
+        assert_eq!(parsed.srp_b, "srp_b");
+        assert_eq!(parsed.salt, "salt");
+        assert_eq!(parsed.secret_block, "secret_block");
+    }
+}
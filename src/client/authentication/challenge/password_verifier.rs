@@ -1,6 +1,6 @@
 use crate::AuthenticationError;
 use crate::authentication::LoginSession;
-use crate::constants::CLIENT_ID;
+use crate::client::authentication::challenge::parameters::ChallengeParameters;
 use aws_cognito_srp::SrpClient;
 use aws_sdk_cognitoidentityprovider::operation::respond_to_auth_challenge::RespondToAuthChallengeOutput;
 use aws_sdk_cognitoidentityprovider::types::ChallengeNameType;
@@ -8,23 +8,21 @@ use std::collections::HashMap;
 
 pub async fn respond_to_challenge(
     cognito_client: &aws_sdk_cognitoidentityprovider::Client,
+    client_id: &str,
     user_srp_client: &SrpClient<aws_cognito_srp::User>,
     device_srp_client: Option<&SrpClient<aws_cognito_srp::TrackedDevice>>,
     session: &mut LoginSession,
     parameters: HashMap<String, String>,
 ) -> Result<RespondToAuthChallengeOutput, AuthenticationError> {
-    let secret_block = parameters.get("SECRET_BLOCK").ok_or_else(|| {
-        AuthenticationError::MissingChallengeParameter("SECRET_BLOCK".to_string())
-    })?;
-    let user_id = parameters.get("USER_ID_FOR_SRP").ok_or_else(|| {
-        AuthenticationError::MissingChallengeParameter("USER_ID_FOR_SRP".to_string())
-    })?;
-    let salt = parameters
-        .get("SALT")
-        .ok_or_else(|| AuthenticationError::MissingChallengeParameter("SALT".to_string()))?;
-    let srp_b = parameters
-        .get("SRP_B")
-        .ok_or_else(|| AuthenticationError::MissingChallengeParameter("SRP_B".to_string()))?;
+    let (
+        ChallengeParameters {
+            srp_b,
+            salt,
+            secret_block,
+        },
+        user_id,
+    ) = ChallengeParameters::parse_with_user_id(&parameters)
+        .map_err(AuthenticationError::MissingChallengeParameter)?;
 
     // Its very important to record the user id here, as, although authentication will
     // succeed without using the user id for the SRP, the device confirmation will fail silently
@@ -39,7 +37,7 @@ pub async fn respond_to_challenge(
         .respond_to_auth_challenge()
         .challenge_name(ChallengeNameType::PasswordVerifier)
         .set_session(session.1.clone())
-        .client_id(CLIENT_ID)
+        .client_id(client_id)
         .challenge_responses("USERNAME", user_id)
         .challenge_responses(
             "PASSWORD_CLAIM_SECRET_BLOCK",
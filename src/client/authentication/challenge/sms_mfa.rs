@@ -1,12 +1,14 @@
 use crate::AuthenticationError;
 use crate::authentication::LoginSession;
-use crate::constants::CLIENT_ID;
 use aws_cognito_srp::SrpClient;
-use aws_sdk_cognitoidentityprovider::operation::respond_to_auth_challenge::RespondToAuthChallengeOutput;
+use aws_sdk_cognitoidentityprovider::operation::respond_to_auth_challenge::{
+    RespondToAuthChallengeError, RespondToAuthChallengeOutput,
+};
 use aws_sdk_cognitoidentityprovider::types::ChallengeNameType;
 
 pub async fn handle_challenge(
     cognito_client: &aws_sdk_cognitoidentityprovider::Client,
+    client_id: &str,
     device_srp_client: Option<&SrpClient<aws_cognito_srp::TrackedDevice>>,
     session: &LoginSession,
     code: &str,
@@ -16,7 +18,7 @@ pub async fn handle_challenge(
         .challenge_responses("SMS_MFA_CODE", code)
         .challenge_responses("USERNAME", session.0.clone())
         .set_session(Option::clone(&session.1))
-        .client_id(CLIENT_ID)
+        .client_id(client_id)
         .challenge_name(ChallengeNameType::SmsMfa);
 
     if let Some(device_key) = device_srp_client
@@ -25,5 +27,52 @@ pub async fn handle_challenge(
         builder = builder.challenge_responses("DEVICE_KEY", device_key);
     }
 
-    Ok(builder.send().await?)
+    builder.send().await.map_err(|error| {
+        if error
+            .as_service_error()
+            .is_some_and(is_attempts_exhausted_rejection)
+        {
+            AuthenticationError::MfaAttemptsExhausted
+        } else {
+            AuthenticationError::from(error)
+        }
+    })
+}
+
+/// Whether Cognito rejected a SMS MFA code because the session has run out of attempts.
+///
+/// Cognito invalidates the session once too many incorrect codes have been entered, which is
+/// distinguished from the session simply expiring by its message.
+fn is_attempts_exhausted_rejection(error: &RespondToAuthChallengeError) -> bool {
+    error.is_not_authorized_exception()
+        && !error
+            .meta()
+            .message()
+            .is_some_and(|message| message.to_lowercase().contains("expired"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aws_sdk_cognitoidentityprovider::error::ErrorMetadata;
+    use aws_sdk_cognitoidentityprovider::types::error::NotAuthorizedException;
+
+    fn not_authorized(message: &str) -> RespondToAuthChallengeError {
+        RespondToAuthChallengeError::NotAuthorizedException(
+            NotAuthorizedException::builder()
+                .message(message)
+                .meta(ErrorMetadata::builder().message(message).build())
+                .build(),
+        )
+    }
+
+    #[test]
+    fn test_exhausted_attempts_are_distinguished_from_expired_sessions() {
+        assert!(is_attempts_exhausted_rejection(&not_authorized(
+            "Invalid session for the user."
+        )));
+        assert!(!is_attempts_exhausted_rejection(&not_authorized(
+            "Invalid session for the user, session is expired."
+        )));
+    }
 }
@@ -0,0 +1,28 @@
+use crate::client::authentication::DEFAULT_MAX_THROTTLE_WAIT;
+use crate::constants;
+use std::time::Duration;
+
+/// The AWS Cognito User Pool which users are authenticated against.
+///
+/// This is Hive's own User Pool by default (see [`constants`]), but can be overridden - for
+/// example, to run tests against a different mock User Pool, without recompiling.
+#[derive(Debug, Clone)]
+pub struct CognitoConfig {
+    pub region: String,
+    pub pool_id: String,
+    pub client_id: String,
+
+    /// The longest to spend backing off from Cognito's rate limits, during a login or refresh.
+    pub max_throttle_wait: Duration,
+}
+
+impl Default for CognitoConfig {
+    fn default() -> Self {
+        Self {
+            region: constants::REGION.to_string(),
+            pool_id: constants::POOL_ID.to_string(),
+            client_id: constants::CLIENT_ID.to_string(),
+            max_throttle_wait: DEFAULT_MAX_THROTTLE_WAIT,
+        }
+    }
+}
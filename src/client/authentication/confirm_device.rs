@@ -1,7 +1,6 @@
 use crate::authentication::user::UntrustedDevice;
 use crate::client::authentication::TrustedDevice;
 use crate::client::authentication::{HiveAuth, Tokens};
-use crate::constants;
 use aws_cognito_srp::{PasswordVerifierParameters, SrpClient};
 use aws_sdk_cognitoidentityprovider::operation::confirm_device::ConfirmDeviceOutput;
 use aws_sdk_cognitoidentityprovider::types::DeviceRememberedStatusType;
@@ -21,11 +20,11 @@ impl HiveAuth {
 
         let srp_client = SrpClient::new(
             aws_cognito_srp::UntrackedDevice::new(
-                constants::POOL_ID,
+                &self.config.pool_id,
                 &untrusted_device.device_group_key,
                 &untrusted_device.device_key,
             ),
-            constants::CLIENT_ID,
+            &self.config.client_id,
             None,
         );
 
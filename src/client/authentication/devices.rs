@@ -0,0 +1,160 @@
+use crate::AuthenticationError;
+use crate::client::authentication::{HiveAuth, Tokens};
+use crate::helper::target;
+use aws_sdk_cognitoidentityprovider::types::DeviceType;
+use chrono::{DateTime, Utc};
+use std::time::Duration;
+
+/// The most devices Cognito will return in a single page when listing devices.
+const DEVICES_PER_PAGE: i32 = 60;
+
+/// A device remembered by the Hive authentication servers, and when it last authenticated.
+#[derive(Debug)]
+struct RememberedDevice {
+    device_key: String,
+    last_authenticated_at: Option<DateTime<Utc>>,
+}
+
+impl RememberedDevice {
+    fn from_device_type(device: &DeviceType) -> Option<Self> {
+        let last_authenticated_at = device
+            .device_last_authenticated_date()
+            .or_else(|| device.device_create_date())
+            .and_then(|date| DateTime::from_timestamp(date.secs(), date.subsec_nanos()));
+
+        Some(Self {
+            device_key: device.device_key()?.to_string(),
+            last_authenticated_at,
+        })
+    }
+}
+
+/// The keys of the devices which have not authenticated since the cutoff.
+///
+/// The device currently being used to authenticate is never considered stale, and neither
+/// are devices which don't report when they last authenticated.
+fn stale_device_keys(
+    devices: Vec<RememberedDevice>,
+    cutoff: DateTime<Utc>,
+    current_device_key: Option<&str>,
+) -> Vec<String> {
+    devices
+        .into_iter()
+        .filter(|device| Some(device.device_key.as_str()) != current_device_key)
+        .filter(|device| {
+            device
+                .last_authenticated_at
+                .is_some_and(|last_authenticated_at| last_authenticated_at < cutoff)
+        })
+        .map(|device| device.device_key)
+        .collect()
+}
+
+impl HiveAuth {
+    async fn list_remembered_devices(
+        &self,
+        tokens: &Tokens,
+    ) -> Result<Vec<RememberedDevice>, AuthenticationError> {
+        let mut devices = Vec::new();
+        let mut pagination_token = None;
+
+        loop {
+            let response = self
+                .cognito
+                .list_devices()
+                .access_token(&tokens.access_token)
+                .limit(DEVICES_PER_PAGE)
+                .set_pagination_token(pagination_token)
+                .send()
+                .await?;
+
+            devices.extend(
+                response
+                    .devices()
+                    .iter()
+                    .filter_map(RememberedDevice::from_device_type),
+            );
+
+            pagination_token = response.pagination_token().map(ToString::to_string);
+
+            if pagination_token.is_none() {
+                return Ok(devices);
+            }
+        }
+    }
+
+    pub async fn prune_devices(
+        &self,
+        tokens: &Tokens,
+        older_than: Duration,
+    ) -> Result<Vec<String>, AuthenticationError> {
+        // A window longer than the history of the clock leaves nothing to prune.
+        let cutoff = chrono::Duration::from_std(older_than)
+            .ok()
+            .and_then(|older_than| self.clock.now().checked_sub_signed(older_than))
+            .unwrap_or(DateTime::<Utc>::MIN_UTC);
+
+        let stale_device_keys = stale_device_keys(
+            self.list_remembered_devices(tokens).await?,
+            cutoff,
+            self.device_key().as_deref(),
+        );
+
+        let mut forgotten = Vec::new();
+        let mut failed = Vec::new();
+
+        for device_key in stale_device_keys {
+            match self
+                .cognito
+                .forget_device()
+                .access_token(&tokens.access_token)
+                .device_key(&device_key)
+                .send()
+                .await
+            {
+                Ok(_) => forgotten.push(device_key),
+                Err(err) => {
+                    log::error!(target: target::AUTH, "Failed to forget device {device_key}: {err}");
+
+                    failed.push((device_key, err.to_string()));
+                }
+            }
+        }
+
+        if failed.is_empty() {
+            Ok(forgotten)
+        } else {
+            Err(AuthenticationError::PruneIncomplete { forgotten, failed })
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_only_stale_devices_are_pruned() {
+        let cutoff = DateTime::from_timestamp(1_000_000, 0).expect("Cutoff should be valid");
+
+        let device = |device_key: &str, last_authenticated_at: Option<i64>| RememberedDevice {
+            device_key: device_key.to_string(),
+            last_authenticated_at: last_authenticated_at
+                .and_then(|timestamp| DateTime::from_timestamp(timestamp, 0)),
+        };
+
+        assert_eq!(
+            stale_device_keys(
+                vec![
+                    device("stale", Some(10)),
+                    device("recent", Some(2_000_000)),
+                    device("current", Some(10)),
+                    device("unknown", None),
+                ],
+                cutoff,
+                Some("current"),
+            ),
+            ["stale"]
+        );
+    }
+}
@@ -30,8 +30,9 @@ pub enum AuthenticationError {
         SdkError<aws_sdk_cognitoidentityprovider::operation::respond_to_auth_challenge::RespondToAuthChallengeError>,
     ),
 
-    #[error("The challenge was not handled correctly")]
-    /// A parameter which was expected to be present in the challenge was not found.
+    #[error("The challenge was missing the parameters: {0}")]
+    /// Parameters which were expected to be present in the challenge were not found (listed
+    /// together, separated by commas).
     MissingChallengeParameter(String),
 
     #[error("An error occurred while trying to authenticate the user")]
@@ -53,9 +54,58 @@ pub enum AuthenticationError {
         DeviceConfirmationError
     ),
 
+    #[error("The login did not complete within {0:?}")]
+    /// The login did not complete within the [login timeout](crate::Client::with_login_timeout),
+    /// so the login in progress was discarded.
+    Timeout(std::time::Duration),
+
+    #[error("The device key {0} was not issued by the Hive user pool's region")]
+    /// The key of the trusted device does not start with the region of the Hive user pool the
+    /// client is configured with, which usually means the keys of the trusted device have been
+    /// mixed up.
+    InvalidDeviceKey(String),
+
     #[error("There is currently no valid authentication in progress")]
     /// There is no authentication flow currently in progress, and the user is not logged in.
     NoAuthenticationInProgress,
+
+    #[error(transparent)]
+    /// The request to retrieve the user's details (such as their MFA preferences) failed.
+    GetUserFailed(
+        #[from]
+        SdkError<aws_sdk_cognitoidentityprovider::operation::get_user::GetUserError>,
+    ),
+
+    #[error("The challenge response is not valid")]
+    /// The response to a challenge is malformed, and was not sent to the Hive authentication
+    /// servers.
+    ///
+    /// For example, a [`crate::authentication::ChallengeResponse::SmsMfa`] code which is not
+    /// exactly six digits.
+    InvalidChallengeResponse,
+
+    #[error(transparent)]
+    /// The request to list the devices remembered by the Hive authentication servers failed.
+    ListDevicesFailed(
+        #[from]
+        SdkError<aws_sdk_cognitoidentityprovider::operation::list_devices::ListDevicesError>,
+    ),
+
+    #[error("{} stale device(s) could not be forgotten", failed.len())]
+    /// Some of the stale devices found by [`crate::Client::prune_devices`] could not be
+    /// forgotten.
+    PruneIncomplete {
+        /// The keys of the devices which were forgotten.
+        forgotten: Vec<String>,
+
+        /// The keys of the devices which could not be forgotten, with the reason why.
+        failed: Vec<(String, String)>,
+    },
+
+    #[error("There are no attempts remaining to respond to the MFA challenge")]
+    /// Too many incorrect MFA codes were entered, so the Hive authentication servers have
+    /// invalidated the login. A new login must be started.
+    MfaAttemptsExhausted,
 }
 
 #[derive(Error, Debug)]
@@ -73,11 +123,36 @@ pub enum RefreshError {
     /// The request to refresh the authentication tokens failed.
     RequestFailed(String),
 
+    #[error(
+        "The request to refresh the authentication tokens failed as the device key was rejected"
+    )]
+    /// The request to refresh the authentication tokens failed as Hive rejected the key of the
+    /// trusted device that was used (for example, because the device has since been forgotten).
+    InvalidDeviceKey(String),
+
+    #[error(
+        "The request to refresh the authentication tokens failed as the refresh token is invalid"
+    )]
+    /// The request to refresh the authentication tokens failed as Hive rejected the refresh
+    /// token (for example, because it has expired or been revoked).
+    ///
+    /// The tokens can't be refreshed, so the user must login again.
+    RefreshTokenInvalid,
+
     #[error("There is currently no valid authentication in progress")]
     /// There is no authentication flow currently in progress, and the user is not logged in.
     NotLoggedIn,
 }
 
+impl RefreshError {
+    /// Whether the user must login again (with [`crate::Client::login`]) before the tokens
+    /// can be used, rather than the refresh failing for a reason which may be temporary.
+    #[must_use]
+    pub const fn requires_login(&self) -> bool {
+        matches!(self, Self::RefreshTokenInvalid | Self::NotLoggedIn)
+    }
+}
+
 #[derive(Error, Debug)]
 #[error(transparent)]
 /// Errors that can occur while trying to confirm a device in order to
@@ -100,4 +175,23 @@ pub enum DeviceConfirmationError {
     #[error("The device being confirmed is already tracked")]
     /// The device being confirmed is already tracked, meaning no confirmation is needed.
     DeviceAlreadyTracked,
+
+    #[error("There is no trusted device to confirm again")]
+    /// The client is not using a trusted device, so there is no device to confirm again.
+    NoTrustedDevice,
+}
+
+#[derive(Error, Debug)]
+#[non_exhaustive]
+/// Errors that can occur while trying to build a [`crate::authentication::TrustedDevice`].
+pub enum TrustedDeviceError {
+    #[error("The {0} of the trusted device was not provided")]
+    /// One of the keys required for the trusted device was not provided.
+    MissingKey(&'static str),
+
+    #[error("The device key {0} was not issued by the user pool")]
+    /// The device key is not in the format issued by AWS Cognito, or does not start with the
+    /// region of the user pool, which usually means the keys of the trusted device have been
+    /// mixed up.
+    InvalidDeviceKey(String),
 }
@@ -1,13 +1,45 @@
+use crate::AuthenticationError;
 use crate::authentication::user::UntrustedDevice;
-use crate::client::authentication::{ChallengeResponse, HiveAuth, Tokens};
-use crate::{AuthenticationError, constants};
+use crate::client::authentication::{
+    ChallengeRequest, ChallengeResponse, HiveAuth, Tokens, TrustedDevice,
+};
 use aws_sdk_cognitoidentityprovider::types::{
     AuthFlowType, AuthenticationResultType, ChallengeNameType, NewDeviceMetadataType,
 };
+use std::time::Duration;
+
+/// The default for the longest a [`crate::Client`] will wait for a step of the login flow to
+/// complete. See [`crate::Client::with_login_timeout`].
+pub const DEFAULT_LOGIN_TIMEOUT: Duration = Duration::from_secs(60);
 
 #[derive(Debug, Default)]
 pub struct LoginSession(pub String, pub Option<String>);
 
+#[derive(Debug)]
+#[non_exhaustive]
+/// The outcome of a successful authentication with Hive.
+pub enum LoginOutcome {
+    /// Hive issued a new device during authentication, which has now been confirmed as a
+    /// [`TrustedDevice`].
+    ///
+    /// The trusted device should be persisted by the caller, so that it can be provided
+    /// during future logins.
+    NewDevice(TrustedDevice),
+
+    /// The trusted device provided during login was reused, so there is nothing new
+    /// to persist.
+    ExistingDevice,
+
+    /// No trusted device was provided during login, and Hive did not issue a new device.
+    NoDevice,
+
+    /// Hive issued a new device during authentication, which was not confirmed because the
+    /// client is configured not to [confirm new devices](crate::Client::confirm_new_devices).
+    ///
+    /// The device can still be confirmed later with [`crate::Client::confirm_device`].
+    UnconfirmedDevice(UntrustedDevice),
+}
+
 impl HiveAuth {
     /// Login to the Hive API using the provided user credentials.
     ///
@@ -17,27 +49,37 @@ impl HiveAuth {
     /// # Errors
     ///
     /// Returns an error if the authentication fails, or if the user is not registered with the Hive API.
+    ///
+    /// Returns [`AuthenticationError::InvalidDeviceKey`] if the key of the trusted device was not
+    /// issued in the region of the user pool.
     pub async fn login(&self) -> Result<(Tokens, Option<UntrustedDevice>), AuthenticationError> {
-        let aws_cognito_srp::UserAuthenticationParameters { a, username, .. } =
-            self.user_srp_client.get_auth_parameters();
+        // Device keys issued by AWS Cognito are always prefixed with the region of the
+        // user pool (i.e. "eu-west-1_...").
+        if let Some(device_key) = self.device_key()
+            && !device_key.starts_with(&format!("{}_", self.config.region))
+        {
+            return Err(AuthenticationError::InvalidDeviceKey(device_key));
+        }
+
+        let aws_cognito_srp::UserAuthenticationParameters { a, username, .. } = self
+            .user_srp_client
+            .as_ref()
+            .ok_or(AuthenticationError::NoAuthenticationInProgress)?
+            .get_auth_parameters();
 
         let mut builder = self
             .cognito
             .initiate_auth()
             .auth_flow(AuthFlowType::UserSrpAuth)
-            .client_id(constants::CLIENT_ID)
+            .client_id(&self.config.client_id)
             .auth_parameters("SRP_A", &a)
             .auth_parameters("USERNAME", &username);
 
-        if let Some(device_key) = self
-            .device_srp_client
-            .as_ref()
-            .map(|device_srp_client| device_srp_client.get_auth_parameters().device_key)
-        {
+        if let Some(device_key) = self.device_key() {
             builder = builder.auth_parameters("DEVICE_KEY", device_key);
         }
 
-        let response = builder.send().await?;
+        let response = self.initiate_auth_with_backoff(builder).await?;
 
         {
             self.session
@@ -70,7 +112,13 @@ impl HiveAuth {
                     }
 
                     Ok((
-                        Tokens::new(id_token, access_token, refresh_token, expires_in),
+                        Tokens::new(
+                            id_token,
+                            access_token,
+                            refresh_token,
+                            expires_in,
+                            &*self.clock,
+                        ),
                         untrusted_device,
                     ))
                 } else {
@@ -83,6 +131,9 @@ impl HiveAuth {
                 ))
                 .await
             }
+            Some(ChallengeNameType::CustomChallenge) => Err(AuthenticationError::NextChallenge(
+                ChallengeRequest::custom(response.challenge_parameters),
+            )),
             Some(name) => Err(AuthenticationError::UnsupportedChallenge(name.to_string())),
         }
     }
@@ -0,0 +1,184 @@
+use crate::AuthenticationError;
+use crate::authentication::ChallengeRequest;
+use crate::client::authentication::{HiveAuth, Tokens};
+use aws_sdk_cognitoidentityprovider::operation::get_user::GetUserOutput;
+use aws_sdk_cognitoidentityprovider::types::DeliveryMediumType;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+/// A method of Multi-Factor Authentication (MFA) which can be enabled on a Hive account.
+pub enum MfaMethod {
+    /// A code is sent to the phone number associated with the account, by SMS.
+    ///
+    /// This is answered with a [`crate::authentication::ChallengeResponse::SmsMfa`].
+    Sms,
+
+    /// A code is generated by an authenticator app.
+    SoftwareToken,
+
+    /// A method which is yet to be mapped by the crate.
+    Unknown(String),
+}
+
+impl From<&str> for MfaMethod {
+    fn from(method: &str) -> Self {
+        match method {
+            "SMS_MFA" => Self::Sms,
+            "SOFTWARE_TOKEN_MFA" => Self::SoftwareToken,
+            _ => Self::Unknown(method.to_string()),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+#[non_exhaustive]
+/// The Multi-Factor Authentication (MFA) settings of a Hive account.
+pub struct MfaPreferences {
+    /// The MFA methods which are enabled on the account.
+    pub enabled: Vec<MfaMethod>,
+
+    /// The MFA method which the account prefers to be challenged with, if any.
+    pub preferred: Option<MfaMethod>,
+}
+
+impl MfaPreferences {
+    /// Whether any MFA method is enabled on the account - in which case logging in without a
+    /// trusted device should be expected to issue a challenge.
+    #[must_use]
+    pub const fn is_enabled(&self) -> bool {
+        !self.enabled.is_empty()
+    }
+
+    /// The challenges which logging in without a trusted device should be expected to issue,
+    /// based on the MFA methods enabled on the account.
+    ///
+    /// Challenges which are handled transparently by the crate (such as the password verifier)
+    /// are not included.
+    #[must_use]
+    pub fn expected_challenges(&self) -> Vec<ChallengeRequest> {
+        let method = match (&self.preferred, self.enabled.as_slice()) {
+            (Some(method), _) | (None, [method]) => method,
+            (None, []) => return vec![],
+            // Cognito asks which method to use when several are enabled, and none is preferred.
+            (None, _) => return vec![ChallengeRequest::Unsupported("SELECT_MFA_TYPE".to_string())],
+        };
+
+        vec![match method {
+            MfaMethod::Sms => ChallengeRequest::SmsMfa {
+                attempts_remaining: None,
+            },
+            MfaMethod::SoftwareToken => {
+                ChallengeRequest::Unsupported("SOFTWARE_TOKEN_MFA".to_string())
+            }
+            MfaMethod::Unknown(method) => ChallengeRequest::Unsupported(method.clone()),
+        }]
+    }
+}
+
+impl From<&GetUserOutput> for MfaPreferences {
+    fn from(user: &GetUserOutput) -> Self {
+        let mut enabled: Vec<MfaMethod> = user
+            .user_mfa_setting_list()
+            .iter()
+            .map(|method| MfaMethod::from(method.as_str()))
+            .collect();
+
+        // Accounts which enabled SMS MFA before MFA settings were introduced in Cognito only
+        // report it through the (deprecated) MFA options.
+        if !enabled.contains(&MfaMethod::Sms)
+            && user
+                .mfa_options()
+                .iter()
+                .any(|option| option.delivery_medium() == Some(&DeliveryMediumType::Sms))
+        {
+            enabled.push(MfaMethod::Sms);
+        }
+
+        Self {
+            enabled,
+            preferred: user.preferred_mfa_setting().map(MfaMethod::from),
+        }
+    }
+}
+
+impl HiveAuth {
+    pub async fn get_mfa_preferences(
+        &self,
+        tokens: &Tokens,
+    ) -> Result<MfaPreferences, AuthenticationError> {
+        let user = self
+            .cognito
+            .get_user()
+            .access_token(&tokens.access_token)
+            .send()
+            .await?;
+
+        Ok(MfaPreferences::from(&user))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aws_sdk_cognitoidentityprovider::types::MfaOptionType;
+
+    #[test]
+    fn test_mfa_preferences_from_user() {
+        let user = GetUserOutput::builder()
+            .username("example")
+            .set_user_attributes(Some(vec![]))
+            .user_mfa_setting_list("SOFTWARE_TOKEN_MFA")
+            .preferred_mfa_setting("SOFTWARE_TOKEN_MFA")
+            .mfa_options(
+                MfaOptionType::builder()
+                    .delivery_medium(DeliveryMediumType::Sms)
+                    .build(),
+            )
+            .build()
+            .expect("User should be built");
+
+        let preferences = MfaPreferences::from(&user);
+
+        assert!(preferences.is_enabled());
+        assert_eq!(
+            preferences.enabled,
+            [MfaMethod::SoftwareToken, MfaMethod::Sms]
+        );
+        assert_eq!(preferences.preferred, Some(MfaMethod::SoftwareToken));
+
+        let user = GetUserOutput::builder()
+            .username("example")
+            .set_user_attributes(Some(vec![]))
+            .build()
+            .expect("User should be built");
+
+        assert!(!MfaPreferences::from(&user).is_enabled());
+    }
+
+    #[test]
+    fn test_expected_challenges() {
+        let preferences = |enabled: Vec<MfaMethod>, preferred: Option<MfaMethod>| MfaPreferences {
+            enabled,
+            preferred,
+        };
+
+        assert!(preferences(vec![], None).expected_challenges().is_empty());
+        assert!(matches!(
+            preferences(vec![MfaMethod::Sms], None).expected_challenges()[..],
+            [ChallengeRequest::SmsMfa { .. }]
+        ));
+        assert!(matches!(
+            &preferences(
+                vec![MfaMethod::Sms, MfaMethod::SoftwareToken],
+                Some(MfaMethod::SoftwareToken)
+            )
+            .expected_challenges()[..],
+            [ChallengeRequest::Unsupported(name)] if name == "SOFTWARE_TOKEN_MFA"
+        ));
+        assert!(matches!(
+            &preferences(vec![MfaMethod::Sms, MfaMethod::SoftwareToken], None)
+                .expected_challenges()[..],
+            [ChallengeRequest::Unsupported(name)] if name == "SELECT_MFA_TYPE"
+        ));
+    }
+}
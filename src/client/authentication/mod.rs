@@ -1,48 +1,88 @@
-use crate::constants;
+use crate::Clock;
 use aws_cognito_srp::{SrpClient, TrackedDevice};
-use aws_config::BehaviorVersion;
+use aws_config::{BehaviorVersion, Region};
 use std::sync::Arc;
 use tokio::sync::RwLock;
 
+mod backoff;
 mod challenge;
+mod config;
 mod confirm_device;
+mod devices;
 mod error;
 mod login;
+mod mfa;
 mod refresh;
+mod session;
+mod timezone;
 mod user;
 
+pub use backoff::DEFAULT_MAX_THROTTLE_WAIT;
 pub use challenge::{ChallengeRequest, ChallengeResponse};
-pub use error::{AuthenticationError, DeviceConfirmationError, RefreshError};
-pub use user::{TrustedDevice, User};
+pub(crate) use config::CognitoConfig;
+pub use error::{AuthenticationError, DeviceConfirmationError, RefreshError, TrustedDeviceError};
+pub use login::{DEFAULT_LOGIN_TIMEOUT, LoginOutcome};
+pub use mfa::{MfaMethod, MfaPreferences};
+pub use session::SessionExport;
+pub use user::{TrustedDevice, TrustedDeviceBuilder, UntrustedDevice, User};
 
 pub(crate) use login::LoginSession;
-pub(crate) use user::{Tokens, UntrustedDevice};
+pub(crate) use refresh::{CognitoRefresher, Refresher};
+pub(crate) use user::Tokens;
 
 #[derive(Debug)]
 pub(crate) struct HiveAuth {
     cognito: aws_sdk_cognitoidentityprovider::Client,
-    user_srp_client: SrpClient<aws_cognito_srp::User>,
+    // The SRP clients keep their own copies of the passwords, which are not overwritten on drop
+    // by the `zeroize` feature.
+    user_srp_client: Option<SrpClient<aws_cognito_srp::User>>,
     device_srp_client: Option<SrpClient<TrackedDevice>>,
+    trusted_device: Option<TrustedDevice>,
     session: Arc<RwLock<Option<LoginSession>>>,
+    clock: Arc<dyn Clock>,
+    config: CognitoConfig,
 }
 
 impl HiveAuth {
     #[must_use]
-    pub(crate) async fn new(user: &User, trusted_device: Option<&TrustedDevice>) -> Self {
-        let config = aws_config::defaults(BehaviorVersion::latest())
-            .region(constants::REGION)
+    pub(crate) async fn new(
+        user: &User,
+        trusted_device: Option<&TrustedDevice>,
+        clock: Arc<dyn Clock>,
+        config: CognitoConfig,
+    ) -> Self {
+        let mut auth = Self::without_user(trusted_device, clock, config).await;
+
+        auth.user_srp_client = Some(SrpClient::new(
+            aws_cognito_srp::User::new(&auth.config.pool_id, &user.username, &user.password),
+            &auth.config.client_id,
+            None,
+        ));
+
+        auth
+    }
+
+    /// Create an authentication which has no user credentials, and so can only be used to
+    /// refresh tokens which were issued elsewhere.
+    #[must_use]
+    pub(crate) async fn without_user(
+        trusted_device: Option<&TrustedDevice>,
+        clock: Arc<dyn Clock>,
+        config: CognitoConfig,
+    ) -> Self {
+        let sdk_config = aws_config::defaults(BehaviorVersion::latest())
+            .region(Region::new(config.region.clone()))
             .load()
             .await;
 
         let mut auth = Self {
-            cognito: aws_sdk_cognitoidentityprovider::Client::new(&config),
-            user_srp_client: SrpClient::new(
-                aws_cognito_srp::User::new(constants::POOL_ID, &user.username, &user.password),
-                constants::CLIENT_ID,
-                None,
-            ),
+            cognito: aws_sdk_cognitoidentityprovider::Client::new(&sdk_config),
+            user_srp_client: None,
             device_srp_client: None,
+            trusted_device: None,
             session: Arc::new(RwLock::new(None)),
+            clock,
+            config,
         };
 
         auth.replace_trusted_device(trusted_device);
@@ -50,18 +90,182 @@ impl HiveAuth {
         auth
     }
 
+    /// The key of the trusted device currently being used to authenticate (if any).
+    ///
+    /// This is always the key of the most recently confirmed (or provided) trusted device, as it's
+    /// kept in sync by [`HiveAuth::replace_trusted_device`].
+    pub(crate) fn device_key(&self) -> Option<String> {
+        self.device_srp_client
+            .as_ref()
+            .map(|device_srp_client| device_srp_client.get_auth_parameters().device_key)
+    }
+
+    /// The keys of the trusted device currently being used to authenticate (if any), without
+    /// its password - for example, to confirm the device again with a new password.
+    pub(crate) fn device_keys(&self) -> Option<UntrustedDevice> {
+        self.trusted_device.as_ref().map(UntrustedDevice::from)
+    }
+
+    /// The trusted device currently being used to authenticate (if any), including its
+    /// password, so the session can be exported with [`crate::Client::export_session`].
+    pub(crate) fn trusted_device(&self) -> Option<TrustedDevice> {
+        self.trusted_device.clone()
+    }
+
+    /// Whether a trusted device is currently being used to authenticate.
+    pub(crate) const fn has_trusted_device(&self) -> bool {
+        self.device_srp_client.is_some()
+    }
+
     pub(crate) fn replace_trusted_device(&mut self, trusted_device: Option<&TrustedDevice>) {
+        self.trusted_device = trusted_device.cloned();
         self.device_srp_client = trusted_device.map(|trusted_device| {
             SrpClient::new(
                 TrackedDevice::new(
-                    constants::POOL_ID,
+                    &self.config.pool_id,
                     &trusted_device.device_group_key,
                     &trusted_device.device_key,
                     &trusted_device.device_password,
                 ),
-                constants::CLIENT_ID,
+                &self.config.client_id,
                 None,
             )
         });
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Client;
+
+    #[tokio::test]
+    async fn test_authentication_uses_the_injected_user_pool() {
+        let client = Client::new("Home Automation").with_region(
+            "eu-west-2",
+            "eu-west-2_MockPool",
+            "mock-client-id",
+        );
+
+        let auth = HiveAuth::new(
+            &User::new("example@example.com", "example"),
+            None,
+            Arc::clone(&client.clock),
+            client.cognito_config.clone(),
+        )
+        .await;
+
+        assert_eq!(
+            auth.cognito.config().region().map(ToString::to_string),
+            Some("eu-west-2".to_string())
+        );
+        assert_eq!(auth.config.pool_id, "eu-west-2_MockPool");
+        assert_eq!(auth.config.client_id, "mock-client-id");
+    }
+
+    #[tokio::test]
+    async fn test_device_keys_are_checked_against_the_user_pool_region() {
+        let client = Client::new("Home Automation").with_region(
+            "eu-west-2",
+            "eu-west-2_MockPool",
+            "mock-client-id",
+        );
+
+        let trusted_device = TrustedDevice::new(
+            "device_password",
+            "device_group_key",
+            "eu-west-1_00000000-0000-0000-0000-000000000000",
+        );
+
+        let auth = HiveAuth::new(
+            &User::new("example@example.com", "example"),
+            Some(&trusted_device),
+            Arc::clone(&client.clock),
+            client.cognito_config.clone(),
+        )
+        .await;
+
+        assert!(matches!(
+            auth.login().await,
+            Err(AuthenticationError::InvalidDeviceKey(device_key))
+                if device_key == trusted_device.device_key
+        ));
+    }
+
+    /// A refresher which issues placeholder tokens in place of Cognito.
+    #[derive(Debug)]
+    struct PlaceholderRefresher;
+
+    impl Refresher for PlaceholderRefresher {
+        fn refresh<'a>(
+            &'a self,
+            auth: &'a HiveAuth,
+            tokens: Arc<Tokens>,
+        ) -> futures_util::future::BoxFuture<'a, Result<Tokens, RefreshError>> {
+            Box::pin(async move {
+                Ok(Tokens::new(
+                    "id_token".to_string(),
+                    "access_token".to_string(),
+                    tokens.refresh_token.clone(),
+                    3600,
+                    &*auth.clock,
+                ))
+            })
+        }
+    }
+
+    #[tokio::test]
+    async fn test_refresh_token_logins_use_the_configured_client() {
+        let client = Client::new("Home Automation")
+            .with_region("eu-west-2", "eu-west-2_MockPool", "mock-client-id")
+            .with_max_throttle_wait(std::time::Duration::from_secs(5))
+            .with_refresher(Arc::new(PlaceholderRefresher));
+
+        client
+            .login_with_refresh_token("refresh_token", None)
+            .await
+            .expect("Client should be authenticated");
+
+        let auth = client.auth.read().await;
+        let auth = auth.as_ref().expect("Client should be authenticated");
+
+        assert_eq!(auth.config.pool_id, "eu-west-2_MockPool");
+        assert_eq!(
+            auth.config.max_throttle_wait,
+            std::time::Duration::from_secs(5)
+        );
+    }
+
+    #[tokio::test]
+    async fn test_sessions_are_restored_with_the_configured_client() {
+        let client = Client::new("Home Automation")
+            .with_region("eu-west-2", "eu-west-2_MockPool", "mock-client-id")
+            .with_max_throttle_wait(std::time::Duration::from_secs(5));
+
+        client
+            .restore_session(SessionExport::new(
+                &Tokens::new(
+                    "id_token".to_string(),
+                    "access_token".to_string(),
+                    "refresh_token".to_string(),
+                    3600,
+                    &*client.clock,
+                ),
+                None,
+            ))
+            .await;
+
+        let auth = client.auth.read().await;
+        let auth = auth.as_ref().expect("Session should be restored");
+
+        assert_eq!(
+            auth.cognito.config().region().map(ToString::to_string),
+            Some("eu-west-2".to_string())
+        );
+        assert_eq!(auth.config.pool_id, "eu-west-2_MockPool");
+        assert_eq!(
+            auth.config.max_throttle_wait,
+            std::time::Duration::from_secs(5)
+        );
+    }
+}
@@ -1,30 +1,65 @@
+use crate::RefreshError;
 use crate::client::authentication::{HiveAuth, Tokens};
-use crate::{RefreshError, constants};
-use aws_sdk_cognitoidentityprovider::operation::initiate_auth::InitiateAuthOutput;
+use crate::helper::target;
+use aws_sdk_cognitoidentityprovider::operation::initiate_auth::{
+    InitiateAuthError, InitiateAuthOutput,
+};
 use aws_sdk_cognitoidentityprovider::types::{AuthFlowType, AuthenticationResultType};
+use futures_util::future::BoxFuture;
+use std::fmt::Debug;
 use std::sync::Arc;
 
+/// Exchanges a refresh token for a new set of tokens, on behalf of a [`crate::Client`].
+///
+/// Clients refresh with the [`CognitoRefresher`] by default, but (like a [`crate::Clock`]) a
+/// different refresher can be injected - for example, to test refreshes without Cognito.
+pub trait Refresher: Debug + Send + Sync {
+    fn refresh<'a>(
+        &'a self,
+        auth: &'a HiveAuth,
+        tokens: Arc<Tokens>,
+    ) -> BoxFuture<'a, Result<Tokens, RefreshError>>;
+}
+
+/// A [`Refresher`] which refreshes tokens with the Cognito User Pool of the authentication.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct CognitoRefresher;
+
+impl Refresher for CognitoRefresher {
+    fn refresh<'a>(
+        &'a self,
+        auth: &'a HiveAuth,
+        tokens: Arc<Tokens>,
+    ) -> BoxFuture<'a, Result<Tokens, RefreshError>> {
+        Box::pin(auth.refresh_tokens(tokens))
+    }
+}
+
 impl HiveAuth {
     pub async fn refresh_tokens(&self, tokens: Arc<Tokens>) -> Result<Tokens, RefreshError> {
         let mut builder = self
             .cognito
             .initiate_auth()
-            .client_id(constants::CLIENT_ID)
+            .client_id(&self.config.client_id)
             .auth_flow(AuthFlowType::RefreshTokenAuth)
             .auth_parameters("REFRESH_TOKEN", &tokens.refresh_token);
 
-        if let Some(device_key) = self
-            .device_srp_client
-            .as_ref()
-            .map(|device_srp_client| device_srp_client.get_auth_parameters().device_key)
-        {
+        let device_key = self.device_key();
+
+        if let Some(device_key) = &device_key {
             builder = builder.auth_parameters("DEVICE_KEY", device_key);
         }
 
-        let response = builder.send().await.map_err(|err| {
-            log::error!("Failed to refresh tokens: {err}");
-            RefreshError::RequestFailed(err.to_string())
-        })?;
+        let response = self
+            .initiate_auth_with_backoff(builder)
+            .await
+            .map_err(|err| {
+                log::error!(target: target::AUTH, "Failed to refresh tokens: {err}");
+
+                err.as_service_error()
+                    .and_then(|error| rejection(error, device_key))
+                    .unwrap_or_else(|| RefreshError::RequestFailed(err.to_string()))
+            })?;
 
         if let InitiateAuthOutput {
             authentication_result:
@@ -37,16 +72,17 @@ impl HiveAuth {
             ..
         } = response
         {
-            log::info!("New set of tokens generated successfully.");
+            log::info!(target: target::AUTH, "New set of tokens generated successfully.");
 
             Ok(Tokens::new(
                 id_token,
                 access_token,
                 tokens.refresh_token.clone(),
                 expires_in,
+                &*self.clock,
             ))
         } else {
-            log::error!("Refresh token request failed.");
+            log::error!(target: target::AUTH, "Refresh token request failed.");
 
             Err(RefreshError::RequestFailed(
                 "The response does not contain an ID token and access token.".to_string(),
@@ -54,3 +90,63 @@ impl HiveAuth {
         }
     }
 }
+
+/// The reason Cognito rejected a request to refresh the tokens, if it was because of the
+/// credentials it was sent (rather than, for example, a transient failure).
+fn rejection(error: &InitiateAuthError, device_key: Option<String>) -> Option<RefreshError> {
+    match device_key {
+        Some(device_key) if is_device_key_rejection(error) => {
+            Some(RefreshError::InvalidDeviceKey(device_key))
+        }
+        _ if error.is_not_authorized_exception() => Some(RefreshError::RefreshTokenInvalid),
+        _ => None,
+    }
+}
+
+/// Whether Cognito rejected a request because of the device key it was sent.
+///
+/// This happens when the device has been forgotten, or the device key does not belong to the
+/// user the refresh token was issued to.
+fn is_device_key_rejection(error: &InitiateAuthError) -> bool {
+    error.is_resource_not_found_exception()
+        || (error.is_not_authorized_exception()
+            && error
+                .meta()
+                .message()
+                .is_some_and(|message| message.to_lowercase().contains("device")))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aws_sdk_cognitoidentityprovider::error::ErrorMetadata;
+    use aws_sdk_cognitoidentityprovider::types::error::NotAuthorizedException;
+
+    #[test]
+    fn test_revoked_refresh_tokens_require_a_new_login() {
+        let error = |message: &str| {
+            InitiateAuthError::NotAuthorizedException(
+                NotAuthorizedException::builder()
+                    .message(message)
+                    .meta(ErrorMetadata::builder().message(message).build())
+                    .build(),
+            )
+        };
+
+        assert!(matches!(
+            rejection(&error("Refresh Token has been revoked"), None),
+            Some(RefreshError::RefreshTokenInvalid)
+        ));
+        assert!(matches!(
+            rejection(
+                &error("Invalid device key given."),
+                Some("device_key".to_string())
+            ),
+            Some(RefreshError::InvalidDeviceKey(_))
+        ));
+        assert!(
+            rejection(&error("Refresh Token has been revoked"), None)
+                .is_some_and(|error| error.requires_login())
+        );
+    }
+}
@@ -0,0 +1,78 @@
+use crate::authentication::{Tokens, TrustedDevice};
+use crate::helper::redact::Redacted;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::fmt::{Debug, Formatter};
+#[cfg(feature = "zeroize")]
+use zeroize::Zeroize;
+
+/// A snapshot of an authenticated session with Hive, which can be persisted (or handed to
+/// another process) and later resumed with [`crate::Client::restore_session`].
+///
+/// Created with [`crate::Client::export_session`].
+///
+/// **The export contains live credentials.** Anyone holding it can act on the Hive account
+/// until the refresh token is revoked, so it should be stored and transferred with the same
+/// care as the user's password - for example, encrypted at rest and never logged.
+#[derive(Clone, Serialize, Deserialize)]
+#[non_exhaustive]
+pub struct SessionExport {
+    /// The ID token issued by Hive.
+    pub id_token: String,
+
+    /// The access token issued by Hive.
+    pub access_token: String,
+
+    /// The refresh token issued by Hive, used to mint new tokens once the others expire.
+    pub refresh_token: String,
+
+    /// When the ID and access tokens expire.
+    pub expires_at: DateTime<Utc>,
+
+    /// The trusted device the session was authenticated with (if any), which is needed to
+    /// refresh the tokens.
+    pub trusted_device: Option<TrustedDevice>,
+}
+
+impl SessionExport {
+    pub(crate) fn new(tokens: &Tokens, trusted_device: Option<TrustedDevice>) -> Self {
+        Self {
+            id_token: tokens.id_token.clone(),
+            access_token: tokens.access_token.clone(),
+            refresh_token: tokens.refresh_token.clone(),
+            expires_at: tokens.expires_at,
+            trusted_device,
+        }
+    }
+
+    pub(crate) fn tokens(&self) -> Tokens {
+        Tokens {
+            id_token: self.id_token.clone(),
+            access_token: self.access_token.clone(),
+            refresh_token: self.refresh_token.clone(),
+            expires_at: self.expires_at,
+        }
+    }
+}
+
+impl Debug for SessionExport {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("SessionExport")
+            .field("id_token", &Redacted(&self.id_token))
+            .field("access_token", &Redacted(&self.access_token))
+            .field("refresh_token", &Redacted(&self.refresh_token))
+            .field("expires_at", &self.expires_at)
+            .field("trusted_device", &self.trusted_device)
+            .finish()
+    }
+}
+
+#[cfg(feature = "zeroize")]
+impl Drop for SessionExport {
+    fn drop(&mut self) {
+        self.id_token.zeroize();
+        self.access_token.zeroize();
+        self.refresh_token.zeroize();
+    }
+}
@@ -0,0 +1,66 @@
+use crate::AuthenticationError;
+use crate::client::authentication::{HiveAuth, Tokens};
+use aws_sdk_cognitoidentityprovider::operation::get_user::GetUserOutput;
+
+/// The standard Cognito attribute holding the user's time zone, as an IANA time zone name.
+const ZONEINFO_ATTRIBUTE: &str = "zoneinfo";
+
+/// The time zone of the user, as set in their attributes.
+fn zoneinfo(user: &GetUserOutput) -> Option<&str> {
+    user.user_attributes()
+        .iter()
+        .find(|attribute| attribute.name() == ZONEINFO_ATTRIBUTE)
+        .and_then(|attribute| attribute.value())
+}
+
+impl HiveAuth {
+    /// Get the name of the logged in user's time zone (for example, `Europe/London`), if
+    /// one is set.
+    pub async fn get_timezone(
+        &self,
+        tokens: &Tokens,
+    ) -> Result<Option<String>, AuthenticationError> {
+        let user = self
+            .cognito
+            .get_user()
+            .access_token(&tokens.access_token)
+            .send()
+            .await?;
+
+        Ok(zoneinfo(&user).map(ToString::to_string))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aws_sdk_cognitoidentityprovider::types::AttributeType;
+
+    #[test]
+    fn test_timezone_is_read_from_user_attributes() {
+        let attribute = |name: &str, value: &str| {
+            AttributeType::builder()
+                .name(name)
+                .value(value)
+                .build()
+                .expect("Attribute should be built")
+        };
+
+        let user = GetUserOutput::builder()
+            .username("example")
+            .user_attributes(attribute("email", "example@example.com"))
+            .user_attributes(attribute("zoneinfo", "Europe/London"))
+            .build()
+            .expect("User should be built");
+
+        assert_eq!(zoneinfo(&user), Some("Europe/London"));
+
+        let user = GetUserOutput::builder()
+            .username("example")
+            .set_user_attributes(Some(vec![]))
+            .build()
+            .expect("User should be built");
+
+        assert_eq!(zoneinfo(&user), None);
+    }
+}
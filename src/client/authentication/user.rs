@@ -1,8 +1,15 @@
+use crate::Clock;
+use crate::authentication::TrustedDeviceError;
+use crate::constants;
+use crate::helper::redact::Redacted;
 use chrono::{DateTime, Utc};
-use std::fmt::Debug;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::fmt::{Debug, Formatter};
 use std::ops::Add;
+#[cfg(feature = "zeroize")]
+use zeroize::Zeroize;
 
-#[derive(Debug)]
 /// A user registed with a Hive account.
 pub struct User {
     /// The username of the user - this is the email address used
@@ -11,6 +18,22 @@ pub struct User {
     pub(crate) password: String,
 }
 
+impl Debug for User {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("User")
+            .field("username", &self.username)
+            .field("password", &Redacted(&self.password))
+            .finish()
+    }
+}
+
+#[cfg(feature = "zeroize")]
+impl Drop for User {
+    fn drop(&mut self) {
+        self.password.zeroize();
+    }
+}
+
 impl User {
     #[must_use]
     /// Create a new user with the given username and password.
@@ -31,7 +54,7 @@ impl User {
 /// device in AWS Cognito.
 ///
 /// See the [AWS Cognito documentation](https://docs.aws.amazon.com/cognito/latest/developerguide/amazon-cognito-user-pools-device-tracking.html#user-pools-remembered-devices-getting-a-device-key) for more information.
-#[derive(Debug, Clone)]
+#[derive(Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub struct TrustedDevice {
     #[allow(missing_docs)]
     pub device_group_key: String,
@@ -43,10 +66,30 @@ pub struct TrustedDevice {
     pub device_password: String,
 }
 
+impl Debug for TrustedDevice {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("TrustedDevice")
+            .field("device_group_key", &self.device_group_key)
+            .field("device_key", &self.device_key)
+            .field("device_password", &Redacted(&self.device_password))
+            .finish()
+    }
+}
+
+#[cfg(feature = "zeroize")]
+impl Drop for TrustedDevice {
+    fn drop(&mut self) {
+        self.device_password.zeroize();
+    }
+}
+
 impl TrustedDevice {
     #[must_use]
     /// Create a new trusted device which can be used to authenticate the user.
     ///
+    /// The keys are not checked - use [`TrustedDevice::builder`] to check the device key is
+    /// valid before creating the trusted device.
+    ///
     /// ```rust
     /// use hive_client::authentication::{TrustedDevice};
     ///
@@ -68,11 +111,137 @@ impl TrustedDevice {
             device_key: device_key.into(),
         }
     }
+
+    #[must_use]
+    /// Create a builder, which names each of the keys required for a trusted device, and
+    /// checks they are valid before creating the trusted device.
+    ///
+    /// ```rust
+    /// use hive_client::authentication::TrustedDevice;
+    ///
+    /// let trusted_device = TrustedDevice::builder()
+    ///     .device_key("eu-west-1_00000000-0000-0000-0000-000000000000")
+    ///     .device_group_key("device_group_key")
+    ///     .device_password("device_password")
+    ///     .build()
+    ///     .expect("Trusted device should be valid");
+    /// ```
+    pub fn builder() -> TrustedDeviceBuilder {
+        TrustedDeviceBuilder::default()
+    }
+}
+
+#[derive(Default)]
+/// A builder for a [`TrustedDevice`], created with [`TrustedDevice::builder`].
+#[allow(clippy::struct_field_names)]
+pub struct TrustedDeviceBuilder {
+    device_group_key: Option<String>,
+    device_key: Option<String>,
+    device_password: Option<String>,
+    region: Option<String>,
+}
+
+impl Debug for TrustedDeviceBuilder {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("TrustedDeviceBuilder")
+            .field("device_group_key", &self.device_group_key)
+            .field("device_key", &self.device_key)
+            .field(
+                "device_password",
+                &self.device_password.as_deref().map(Redacted),
+            )
+            .field("region", &self.region)
+            .finish()
+    }
+}
+
+impl TrustedDeviceBuilder {
+    #[must_use]
+    /// Set the key of the trusted device.
+    pub fn device_key(mut self, device_key: &str) -> Self {
+        self.device_key = Some(device_key.into());
+        self
+    }
+
+    #[must_use]
+    /// Set the group key of the trusted device.
+    pub fn device_group_key(mut self, device_group_key: &str) -> Self {
+        self.device_group_key = Some(device_group_key.into());
+        self
+    }
+
+    #[must_use]
+    /// Set the password of the trusted device.
+    pub fn device_password(mut self, device_password: &str) -> Self {
+        self.device_password = Some(device_password.into());
+        self
+    }
+
+    #[must_use]
+    /// Set the region of the user pool which issued the trusted device.
+    ///
+    /// Defaults to the region of the Hive user pool.
+    pub fn region(mut self, region: &str) -> Self {
+        self.region = Some(region.into());
+        self
+    }
+
+    /// Create the trusted device.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if any of the keys have not been set, or if the device key is not one
+    /// issued by the user pool (which usually means the keys have been mixed up).
+    pub fn build(self) -> Result<TrustedDevice, TrustedDeviceError> {
+        let device_key = self
+            .device_key
+            .ok_or(TrustedDeviceError::MissingKey("device_key"))?;
+        let device_group_key = self
+            .device_group_key
+            .ok_or(TrustedDeviceError::MissingKey("device_group_key"))?;
+        let device_password = self
+            .device_password
+            .ok_or(TrustedDeviceError::MissingKey("device_password"))?;
+
+        if !is_device_key(
+            &device_key,
+            self.region.as_deref().unwrap_or(constants::REGION),
+        ) {
+            return Err(TrustedDeviceError::InvalidDeviceKey(device_key));
+        }
+
+        Ok(TrustedDevice {
+            device_group_key,
+            device_key,
+            device_password,
+        })
+    }
+}
+
+/// Whether a device key is in the format issued by AWS Cognito - the region of the user pool,
+/// followed by a UUID (i.e. "eu-west-1_00000000-0000-0000-0000-000000000000").
+fn is_device_key(device_key: &str, region: &str) -> bool {
+    device_key
+        .strip_prefix(region)
+        .and_then(|device_key| device_key.strip_prefix('_'))
+        .is_some_and(|uuid| {
+            uuid.len() == 36
+                && uuid.char_indices().all(|(i, c)| match i {
+                    8 | 13 | 18 | 23 => c == '-',
+                    _ => c.is_ascii_hexdigit(),
+                })
+        })
 }
 
 #[derive(Debug)]
+/// A device issued by Hive during login, which is yet to be confirmed as a [`TrustedDevice`].
+///
+/// See [`crate::authentication::LoginOutcome::UnconfirmedDevice`].
 pub struct UntrustedDevice {
+    #[allow(missing_docs)]
     pub device_group_key: String,
+
+    #[allow(missing_docs)]
     pub device_key: String,
 }
 
@@ -86,7 +255,19 @@ impl UntrustedDevice {
     }
 }
 
-#[derive(Debug)]
+impl From<&TrustedDevice> for UntrustedDevice {
+    /// The keys of a trusted device, without its password.
+    fn from(trusted_device: &TrustedDevice) -> Self {
+        Self::new(&trusted_device.device_group_key, &trusted_device.device_key)
+    }
+}
+
+impl From<TrustedDevice> for UntrustedDevice {
+    fn from(trusted_device: TrustedDevice) -> Self {
+        Self::from(&trusted_device)
+    }
+}
+
 pub struct Tokens {
     pub(crate) id_token: String,
     pub(crate) access_token: String,
@@ -94,19 +275,93 @@ pub struct Tokens {
     pub(crate) expires_at: DateTime<Utc>,
 }
 
+impl Debug for Tokens {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Tokens")
+            .field("id_token", &Redacted(&self.id_token))
+            .field("access_token", &Redacted(&self.access_token))
+            .field("refresh_token", &Redacted(&self.refresh_token))
+            .field("expires_at", &self.expires_at)
+            .finish()
+    }
+}
+
+#[cfg(feature = "zeroize")]
+impl Drop for Tokens {
+    fn drop(&mut self) {
+        self.id_token.zeroize();
+        self.access_token.zeroize();
+        self.refresh_token.zeroize();
+    }
+}
+
 impl Tokens {
+    /// How long before the tokens expire that they are considered due to be refreshed.
+    ///
+    /// Refreshing slightly early avoids the tokens expiring while a request is in-flight.
+    pub(crate) const REFRESH_SKEW: chrono::Duration = chrono::Duration::seconds(30);
+
     #[must_use]
     pub fn new(
         id_token: String,
         access_token: String,
         refresh_token: String,
         expires_in: i32,
+        clock: &dyn Clock,
     ) -> Self {
         Self {
             id_token,
             access_token,
             refresh_token,
-            expires_at: Utc::now().add(chrono::Duration::seconds(i64::from(expires_in))),
+            expires_at: clock
+                .now()
+                .add(chrono::Duration::seconds(i64::from(expires_in))),
+        }
+    }
+
+    /// Whether the tokens will have expired within the given window of time from now.
+    #[must_use]
+    pub fn expires_within(&self, window: chrono::Duration, clock: &dyn Clock) -> bool {
+        self.expires_at <= clock.now() + window
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_trusted_device_keys_are_checked() {
+        let builder = || {
+            TrustedDevice::builder()
+                .device_group_key("device_group_key")
+                .device_password("device_password")
+                .region("eu-west-2")
+        };
+
+        let trusted_device = builder()
+            .device_key("eu-west-2_0a1b2c3d-0000-4000-8000-00000000abcd")
+            .build()
+            .expect("Trusted device should be valid");
+
+        let untrusted_device = UntrustedDevice::from(&trusted_device);
+        assert_eq!(untrusted_device.device_key, trusted_device.device_key);
+        assert_eq!(untrusted_device.device_group_key, "device_group_key");
+
+        for device_key in [
+            "eu-west-1_0a1b2c3d-0000-4000-8000-00000000abcd",
+            "eu-west-2_device_key",
+            "device_group_key",
+        ] {
+            assert!(matches!(
+                builder().device_key(device_key).build(),
+                Err(TrustedDeviceError::InvalidDeviceKey(key)) if key == device_key
+            ));
         }
+
+        assert!(matches!(
+            builder().build(),
+            Err(TrustedDeviceError::MissingKey("device_key"))
+        ));
     }
 }
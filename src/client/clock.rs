@@ -0,0 +1,21 @@
+use chrono::{DateTime, Utc};
+use std::fmt::Debug;
+
+/// A source of the current time, used by the [`crate::Client`] when issuing and expiring tokens.
+///
+/// By default the client uses the [`SystemClock`], however a custom clock can be provided
+/// with [`crate::Client::with_clock`] - for example, to advance time deterministically in tests.
+pub trait Clock: Debug + Send + Sync {
+    /// The current date and time.
+    fn now(&self) -> DateTime<Utc>;
+}
+
+/// A [`Clock`] which reads the current time from the system.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct SystemClock;
+
+impl Clock for SystemClock {
+    fn now(&self) -> DateTime<Utc> {
+        Utc::now()
+    }
+}
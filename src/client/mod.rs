@@ -1,4 +1,8 @@
+mod account;
 mod api;
+mod clock;
+mod retry;
+mod scene;
 mod wrapper;
 
 /// Support for the Hive Authentication API.
@@ -9,26 +13,52 @@ pub use api::devices;
 pub use api::products;
 pub use api::weather;
 
-pub use api::ApiError;
-pub use authentication::AuthenticationError;
+#[cfg(feature = "fixtures")]
+pub use api::fixtures;
+
+pub use account::Account;
+pub use api::{ApiError, Correlated, DEFAULT_MAX_RESPONSE_SIZE};
+pub use authentication::{AuthenticationError, DEFAULT_LOGIN_TIMEOUT, DEFAULT_MAX_THROTTLE_WAIT};
+pub use clock::{Clock, SystemClock};
+pub use retry::DEFAULT_RETRY_BUDGET;
+pub use scene::{Scene, SceneEntry};
 
 #[doc(hidden)]
 pub use authentication::RefreshError;
 
 use crate::authentication::HiveAuth;
 use crate::client::api::HiveApi;
-use crate::client::authentication::{Tokens, User};
+use crate::client::authentication::{CognitoConfig, CognitoRefresher, Refresher, Tokens, User};
+use crate::client::retry::RetryBudget;
+use crate::weather::Weather;
+use std::collections::HashMap;
 use std::sync::Arc;
+use std::time::Duration;
 use tokio::sync::{Mutex, RwLock};
 
 /// Client used to authenticate and interact with Hive.
+///
+/// Each client holds all of its own authentication and connection state, so multiple clients
+/// (for example, one per Hive account) can be used concurrently within the same process.
 #[derive(Debug)]
+// Each of the flags is an independent option, set with its own builder method.
+#[allow(clippy::struct_excessive_bools)]
 pub struct Client {
     auth: RwLock<Option<HiveAuth>>,
     api: HiveApi,
     user: Mutex<Option<User>>,
     tokens: Mutex<Option<Arc<Tokens>>>,
     friendly_name: String,
+    clock: Arc<dyn Clock>,
+    refresher: Arc<dyn Refresher>,
+    last_known_weather: Mutex<HashMap<String, Weather>>,
+    cognito_config: CognitoConfig,
+    fail_on_unknown: bool,
+    reject_offline_changes: bool,
+    include_disabled_products: bool,
+    confirm_new_devices: bool,
+    retry_budget: RetryBudget,
+    login_timeout: Duration,
 }
 
 impl Client {
@@ -39,32 +69,392 @@ impl Client {
     /// the user is authenticating for the first time (does not have a trusted device during [`Client::login`])
     #[must_use]
     pub fn new(friendly_name: &str) -> Self {
+        Self::with_clock(friendly_name, Arc::new(SystemClock))
+    }
+
+    /// Create a new client which uses the given [`Clock`] as its source of the current time.
+    ///
+    /// The clock is used to determine when the authentication tokens issued by Hive have
+    /// expired, and need to be refreshed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use hive_client::SystemClock;
+    /// use std::sync::Arc;
+    ///
+    /// let client = hive_client::Client::with_clock("Home Automation", Arc::new(SystemClock));
+    /// ```
+    #[must_use]
+    pub fn with_clock(friendly_name: &str, clock: Arc<dyn Clock>) -> Self {
         Self {
             auth: RwLock::new(None),
             api: HiveApi::new(),
             user: Mutex::new(None),
             tokens: Mutex::new(None),
             friendly_name: friendly_name.to_string(),
+            clock,
+            refresher: Arc::new(CognitoRefresher),
+            last_known_weather: Mutex::new(HashMap::new()),
+            cognito_config: CognitoConfig::default(),
+            fail_on_unknown: false,
+            reject_offline_changes: false,
+            include_disabled_products: false,
+            confirm_new_devices: true,
+            retry_budget: RetryBudget::new(DEFAULT_RETRY_BUDGET),
+            login_timeout: DEFAULT_LOGIN_TIMEOUT,
         }
     }
+
+    /// Create a new client which serves the products, devices, Quick Actions and weather from
+    /// recorded responses in the given directory, in place of a live Hive account - for
+    /// example, for deterministic tests or offline demos.
+    ///
+    /// The client is already authenticated, so no login is required. Changes (such as setting
+    /// the state of a product, or activating a Quick Action) are never sent to Hive, and are
+    /// instead recorded to be checked with [`Client::recorded_calls`].
+    ///
+    /// See [`fixtures::Fixtures`] for the files the directory should hold.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use hive_client::fixtures::RecordedCall;
+    /// use hive_client::products::{Mode, State, States};
+    ///
+    /// # tokio_test::block_on(async {
+    /// let client = hive_client::Client::from_fixtures("fixtures");
+    ///
+    /// let mut products = client.get_products()
+    ///     .await
+    ///     .expect("Products should be read from the fixtures");
+    ///
+    /// products[0].set_state(&States(vec![State::Mode(Mode::Off)]))
+    ///     .await
+    ///     .expect("State change should be recorded");
+    ///
+    /// assert!(matches!(
+    ///     client.recorded_calls().as_slice(),
+    ///     [RecordedCall::SetState { .. }]
+    /// ));
+    /// # })
+    /// ```
+    #[cfg(feature = "fixtures")]
+    #[must_use]
+    pub fn from_fixtures(directory: impl Into<std::path::PathBuf>) -> Self {
+        let mut client = Self::new("Fixtures");
+
+        client
+            .api
+            .set_fixtures(fixtures::Fixtures::new(directory.into()));
+
+        // Tokens are never sent anywhere, so placeholders which won't expire stand in for a login.
+        client.tokens = Mutex::new(Some(Arc::new(Tokens::new(
+            String::new(),
+            String::new(),
+            String::new(),
+            i32::MAX,
+            &*client.clock,
+        ))));
+
+        client
+    }
+
+    /// The changes requested from a client created with [`Client::from_fixtures`], in the
+    /// order they were requested.
+    ///
+    /// This is always empty for clients which use a live Hive account.
+    #[cfg(feature = "fixtures")]
+    #[must_use]
+    pub fn recorded_calls(&self) -> Vec<fixtures::RecordedCall> {
+        self.api
+            .fixtures()
+            .map(fixtures::Fixtures::calls)
+            .unwrap_or_default()
+    }
+
+    /// Set the maximum size (in bytes) of a response which will be read from the Hive API.
+    ///
+    /// Any larger responses are rejected with [`ApiError::ResponseTooLarge`], which protects
+    /// long-running services from a malformed (or malicious) response exhausting their memory.
+    ///
+    /// Defaults to [`DEFAULT_MAX_RESPONSE_SIZE`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let client = hive_client::Client::new("Home Automation")
+    ///     .with_max_response_size(1024 * 1024);
+    /// ```
+    #[must_use]
+    pub const fn with_max_response_size(mut self, max_response_size: usize) -> Self {
+        self.api.set_max_response_size(max_response_size);
+
+        self
+    }
+
+    /// Set the number of retries the client can make in a burst, across every call it makes.
+    ///
+    /// Each retry (for example, retrying a call after the tokens were rejected) withdraws from
+    /// the budget, and successful calls slowly earn it back. Once the budget runs dry, calls
+    /// fail without being retried - which prevents many concurrent operations from flooding
+    /// Hive with retries during an outage.
+    ///
+    /// Defaults to [`DEFAULT_RETRY_BUDGET`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let client = hive_client::Client::new("Home Automation")
+    ///     .with_retry_budget(5);
+    /// ```
+    #[must_use]
+    pub const fn with_retry_budget(mut self, retries: u32) -> Self {
+        self.retry_budget = RetryBudget::new(retries);
+
+        self
+    }
+
+    /// Confirm new devices issued by Hive during login, so that they become trusted devices.
+    ///
+    /// When disabled, a login which is issued a new device completes with
+    /// [`authentication::LoginOutcome::UnconfirmedDevice`] instead, leaving the caller to decide
+    /// whether to trust it with [`Client::confirm_device`] - for example, for one-off logins on
+    /// a shared machine, which shouldn't add to the trusted devices of the Hive account.
+    ///
+    /// Defaults to `true`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let client = hive_client::Client::new("Home Automation")
+    ///     .confirm_new_devices(false);
+    /// ```
+    #[must_use]
+    pub const fn confirm_new_devices(mut self, confirm_new_devices: bool) -> Self {
+        self.confirm_new_devices = confirm_new_devices;
+
+        self
+    }
+
+    /// Set the longest the client will wait for [`Client::login`] (or
+    /// [`Client::respond_to_challenge`]) to complete, across every request made to Hive along
+    /// the way.
+    ///
+    /// Once the timeout is reached, the login in progress is discarded and the call fails with
+    /// [`AuthenticationError::Timeout`] - which bounds how long an interactive app can be left
+    /// waiting on a login.
+    ///
+    /// Defaults to [`DEFAULT_LOGIN_TIMEOUT`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::time::Duration;
+    ///
+    /// let client = hive_client::Client::new("Home Automation")
+    ///     .with_login_timeout(Duration::from_secs(30));
+    /// ```
+    #[must_use]
+    pub const fn with_login_timeout(mut self, login_timeout: Duration) -> Self {
+        self.login_timeout = login_timeout;
+
+        self
+    }
+
+    /// Set the longest the client will spend backing off from AWS Cognito's rate limits during a
+    /// single login (or refresh of the tokens), before giving up.
+    ///
+    /// When Cognito throttles a request - for example, during a burst of logins while
+    /// reconnecting many clients - the request is retried with an exponential backoff (or after
+    /// the delay requested by Cognito), and only fails once the wait would exceed this limit.
+    ///
+    /// Defaults to [`DEFAULT_MAX_THROTTLE_WAIT`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::time::Duration;
+    ///
+    /// let client = hive_client::Client::new("Home Automation")
+    ///     .with_max_throttle_wait(Duration::from_secs(10));
+    /// ```
+    #[must_use]
+    pub const fn with_max_throttle_wait(mut self, max_throttle_wait: Duration) -> Self {
+        self.cognito_config.max_throttle_wait = max_throttle_wait;
+
+        self
+    }
+
+    /// Reject any products or devices which are yet to be mapped by the crate, rather than
+    /// returning them as [`crate::products::ProductData::Unknown`] or
+    /// [`crate::devices::DeviceData::Unknown`].
+    ///
+    /// When enabled, [`Client::get_products`] and [`Client::get_devices`] fail with
+    /// [`ApiError::UnknownProductType`] or [`ApiError::UnknownDeviceType`], so that consumers
+    /// can be sure they are handling every product and device in the account.
+    ///
+    /// Defaults to `false`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let client = hive_client::Client::new("Home Automation")
+    ///     .fail_on_unknown(true);
+    /// ```
+    #[must_use]
+    pub const fn fail_on_unknown(mut self, fail_on_unknown: bool) -> Self {
+        self.fail_on_unknown = fail_on_unknown;
+
+        self
+    }
+
+    /// Reject changes to the states of products which were offline when they were last
+    /// retrieved, rather than sending the change to Hive.
+    ///
+    /// Hive accepts changes for offline products, but they will not take effect until the
+    /// product reconnects. When enabled, [`crate::products::Product::set_state`] (and the
+    /// methods built on it) returns [`ApiError::DeviceOffline`] instead.
+    ///
+    /// Defaults to `false`, so that changes can be queued for when the product reconnects.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let client = hive_client::Client::new("Home Automation")
+    ///     .reject_offline_changes(true);
+    /// ```
+    #[must_use]
+    pub const fn reject_offline_changes(mut self, reject_offline_changes: bool) -> Self {
+        self.reject_offline_changes = reject_offline_changes;
+
+        self
+    }
+
+    /// Include products which are disabled in the Hive account (for example, those which have
+    /// been decommissioned but not deleted) in [`Client::get_products`].
+    ///
+    /// Defaults to `false`, matching the Hive app - which only shows enabled products. See
+    /// [`crate::products::ProductData::is_enabled`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let client = hive_client::Client::new("Home Automation")
+    ///     .include_disabled_products(true);
+    /// ```
+    #[must_use]
+    pub const fn include_disabled_products(mut self, include_disabled_products: bool) -> Self {
+        self.include_disabled_products = include_disabled_products;
+
+        self
+    }
+
+    /// Authenticate against a different AWS Cognito User Pool to the one set in
+    /// [`crate::constants`] - for example, a second mock User Pool in tests.
+    #[cfg(test)]
+    pub(crate) fn with_region(mut self, region: &str, pool_id: &str, client_id: &str) -> Self {
+        self.cognito_config = CognitoConfig {
+            region: region.to_string(),
+            pool_id: pool_id.to_string(),
+            client_id: client_id.to_string(),
+            max_throttle_wait: self.cognito_config.max_throttle_wait,
+        };
+
+        self
+    }
+
+    /// Refresh tokens with a different [`Refresher`] to Cognito - for example, one which
+    /// counts the refreshes made in tests.
+    #[cfg(test)]
+    pub(crate) fn with_refresher(mut self, refresher: Arc<dyn Refresher>) -> Self {
+        self.refresher = refresher;
+
+        self
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::authentication::{
+        ChallengeResponse, DeviceConfirmationError, LoginOutcome, TrustedDevice, UntrustedDevice,
+    };
+    use chrono::{DateTime, Duration, Utc};
     use dotenvy_macro::dotenv;
 
+    /// A clock which only moves forward when explicitly advanced.
+    #[derive(Debug)]
+    struct MockClock(std::sync::Mutex<DateTime<Utc>>);
+
+    impl MockClock {
+        fn new() -> Self {
+            Self(std::sync::Mutex::new(Utc::now()))
+        }
+
+        fn advance(&self, duration: Duration) {
+            let mut now = self.0.lock().expect("Clock should not be poisoned");
+            *now += duration;
+        }
+    }
+
+    impl Clock for MockClock {
+        fn now(&self) -> DateTime<Utc> {
+            *self.0.lock().expect("Clock should not be poisoned")
+        }
+    }
+
+    /// A refresher which issues placeholder tokens (expiring by the given clock) in place of
+    /// Cognito, counting the refreshes.
+    #[derive(Debug)]
+    struct MockRefresher(Arc<MockClock>, std::sync::atomic::AtomicUsize);
+
+    impl MockRefresher {
+        fn new(clock: Arc<MockClock>) -> Self {
+            Self(clock, std::sync::atomic::AtomicUsize::new(0))
+        }
+
+        fn refreshes(&self) -> usize {
+            self.1.load(std::sync::atomic::Ordering::SeqCst)
+        }
+    }
+
+    impl Refresher for MockRefresher {
+        fn refresh<'a>(
+            &'a self,
+            _auth: &'a HiveAuth,
+            tokens: Arc<Tokens>,
+        ) -> futures_util::future::BoxFuture<'a, Result<Tokens, RefreshError>> {
+            let refresh = self.1.fetch_add(1, std::sync::atomic::Ordering::SeqCst) + 1;
+
+            Box::pin(async move {
+                // Yield, so that concurrent callers are waiting on the refresh while it's in-flight.
+                tokio::task::yield_now().await;
+
+                Ok(Tokens::new(
+                    format!("id_token_{refresh}"),
+                    format!("access_token_{refresh}"),
+                    tokens.refresh_token.clone(),
+                    3600,
+                    &*self.0,
+                ))
+            })
+        }
+    }
+
     #[tokio::test]
     async fn test_cognito_authentication_and_device_confirmation() {
         let mut client = Client::new("Home Automation");
 
         let user = User::new(dotenv!("MOCK_USER_EMAIL"), dotenv!("MOCK_USER_PASSWORD"));
 
-        let trusted_device = client
+        let LoginOutcome::NewDevice(trusted_device) = client
             .login(user, None)
             .await
             .expect("Login should succeed")
-            .expect("A trusted device should've been returned");
+        else {
+            panic!("A new trusted device should've been returned");
+        };
 
         assert!(!trusted_device.device_key.is_empty());
         assert!(!trusted_device.device_group_key.is_empty());
@@ -76,9 +466,256 @@ mod tests {
         client.logout().await;
     }
 
+    #[test]
+    fn test_clients_can_be_shared_across_threads() {
+        const fn assert_send_sync<T: Send + Sync>(_: &T) {}
+        const fn assert_send<T: Send>(_: &T) {}
+
+        let client = Client::new("Home Automation");
+        assert_send_sync(&client);
+
+        // Each client holds all of its own state, so calls to separate clients (or the same
+        // client) can be driven concurrently from any thread.
+        assert_send(&client.login(User::new("example@example.com", "example"), None));
+        assert_send(&client.get_products());
+        assert_send(&client.get_devices());
+        assert_send(&client.get_actions());
+        assert_send(&client.get_weather("SW1A 1AA"));
+    }
+
     #[tokio::test]
-    async fn test_cognito_authentication_refresh() {
+    async fn test_clients_keep_separate_configs_and_tokens() {
+        let first = Client::new("Home Automation").with_region(
+            "eu-west-1",
+            "eu-west-1_FirstPool",
+            "first-client-id",
+        );
+        let second = Client::new("Home Automation").with_region(
+            "eu-west-2",
+            "eu-west-2_SecondPool",
+            "second-client-id",
+        );
+
+        for (client, refresh_token) in [
+            (&first, "first-refresh-token"),
+            (&second, "second-refresh-token"),
+        ] {
+            client.tokens.lock().await.replace(Arc::new(Tokens::new(
+                "id_token".to_string(),
+                "access_token".to_string(),
+                refresh_token.to_string(),
+                3600,
+                &*client.clock,
+            )));
+            client.auth.write().await.replace(
+                HiveAuth::without_user(
+                    None,
+                    Arc::clone(&client.clock),
+                    client.cognito_config.clone(),
+                )
+                .await,
+            );
+        }
+
+        let (first_session, second_session) =
+            tokio::join!(first.export_session(), second.export_session());
+
+        assert_eq!(first.cognito_config.region, "eu-west-1");
+        assert_eq!(first.cognito_config.pool_id, "eu-west-1_FirstPool");
+        assert_eq!(
+            first_session
+                .expect("Session should be exported")
+                .refresh_token,
+            "first-refresh-token"
+        );
+
+        assert_eq!(second.cognito_config.region, "eu-west-2");
+        assert_eq!(second.cognito_config.pool_id, "eu-west-2_SecondPool");
+        assert_eq!(
+            second_session
+                .expect("Session should be exported")
+                .refresh_token,
+            "second-refresh-token"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_tokens_are_only_refreshed_once_expired() {
+        let clock = Arc::new(MockClock::new());
+        let client = Client::with_clock("Home Automation", Arc::clone(&clock) as Arc<dyn Clock>);
+
+        let issued_tokens = Arc::new(Tokens::new(
+            "id_token".to_string(),
+            "access_token".to_string(),
+            "refresh_token".to_string(),
+            3600,
+            &*clock,
+        ));
+        client
+            .tokens
+            .lock()
+            .await
+            .replace(Arc::clone(&issued_tokens));
+
+        // Tokens are refreshed slightly before they expire, to avoid them expiring mid-request.
+        clock.advance(Duration::seconds(3599) - Tokens::REFRESH_SKEW);
+
+        let current_tokens = client
+            .refresh_tokens_if_needed()
+            .await
+            .expect("Unexpired tokens should be returned without a refresh");
+
+        assert!(Arc::ptr_eq(&issued_tokens, &current_tokens));
+        assert!(
+            client
+                .tokens_expire_within(std::time::Duration::from_secs(31))
+                .await
+        );
+
+        // Forcing a refresh ignores the expiry, so fails even though the tokens are unexpired.
+        assert!(matches!(
+            client.refresh_now().await,
+            Err(ApiError::RefreshError(RefreshError::NotLoggedIn))
+        ));
+
+        clock.advance(Duration::seconds(1));
+
+        // There's no authentication in progress, so the expired tokens can't be refreshed.
+        assert!(matches!(
+            client.refresh_tokens_if_needed().await,
+            Err(ApiError::RefreshError(RefreshError::NotLoggedIn))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_cancelling_a_login_in_progress() {
         let mut client = Client::new("Home Automation");
+        let user = User::new("example@example.com", "example");
+
+        client.auth.write().await.replace(
+            HiveAuth::new(
+                &user,
+                None,
+                Arc::clone(&client.clock),
+                client.cognito_config.clone(),
+            )
+            .await,
+        );
+        client.user.lock().await.replace(user);
+
+        client.cancel_login().await;
+
+        assert!(client.auth.read().await.is_none());
+        assert!(client.user.lock().await.is_none());
+        assert!(matches!(
+            client
+                .respond_to_challenge(ChallengeResponse::SmsMfa("123456".to_string()))
+                .await,
+            Err(AuthenticationError::NoAuthenticationInProgress)
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_sessions_can_be_exported_and_restored() {
+        let client = Client::new("Home Automation");
+
+        assert!(client.export_session().await.is_none());
+
+        let trusted_device =
+            TrustedDevice::new("device_password", "device_group_key", "device_key");
+
+        client.tokens.lock().await.replace(Arc::new(Tokens::new(
+            "id_token".to_string(),
+            "access_token".to_string(),
+            "stored-refresh-token".to_string(),
+            3600,
+            &*client.clock,
+        )));
+        client.auth.write().await.replace(
+            HiveAuth::without_user(
+                Some(&trusted_device),
+                Arc::clone(&client.clock),
+                client.cognito_config.clone(),
+            )
+            .await,
+        );
+
+        let exported = client
+            .export_session()
+            .await
+            .expect("Session should be exported");
+        assert!(!format!("{exported:?}").contains("stored-refresh-token"));
+
+        let restored = Client::new("Home Automation");
+        restored
+            .restore_session(
+                serde_json::from_str(
+                    &serde_json::to_string(&exported).expect("Session should serialize"),
+                )
+                .expect("Session should deserialize"),
+            )
+            .await;
+
+        let resumed = restored
+            .export_session()
+            .await
+            .expect("Session should be restored");
+        assert_eq!(resumed.refresh_token, "stored-refresh-token");
+        assert_eq!(resumed.expires_at, exported.expires_at);
+        assert_eq!(resumed.trusted_device, Some(trusted_device));
+    }
+
+    #[tokio::test]
+    async fn test_confirming_a_device_requires_a_login() {
+        let client = Client::new("Home Automation").confirm_new_devices(false);
+
+        assert!(!client.confirm_new_devices);
+        assert!(matches!(
+            client
+                .confirm_device(UntrustedDevice::new("device_group_key", "device_key"))
+                .await,
+            Err(AuthenticationError::NoAuthenticationInProgress)
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_reconfirming_requires_a_trusted_device() {
+        let client = Client::new("Home Automation");
+
+        assert!(matches!(
+            client.reconfirm_device().await,
+            Err(AuthenticationError::NoAuthenticationInProgress)
+        ));
+
+        client.tokens.lock().await.replace(Arc::new(Tokens::new(
+            "id_token".to_string(),
+            "access_token".to_string(),
+            "refresh_token".to_string(),
+            3600,
+            &*client.clock,
+        )));
+        client.auth.write().await.replace(
+            HiveAuth::without_user(
+                None,
+                Arc::clone(&client.clock),
+                client.cognito_config.clone(),
+            )
+            .await,
+        );
+
+        assert!(matches!(
+            client.reconfirm_device().await,
+            Err(AuthenticationError::DeviceConfirmationError(
+                DeviceConfirmationError::NoTrustedDevice
+            ))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_cognito_authentication_refresh() {
+        let clock = Arc::new(MockClock::new());
+        let mut client =
+            Client::with_clock("Home Automation", Arc::clone(&clock) as Arc<dyn Clock>);
 
         let user = User::new(dotenv!("MOCK_USER_EMAIL"), dotenv!("MOCK_USER_PASSWORD"));
 
@@ -87,23 +724,15 @@ mod tests {
             .await
             .expect("Login should succeed");
 
-        let current_tokens = {
-            // Update the tokens to simulate an expiration
-
-            let mut tokens = client.tokens.lock().await;
-
-            let current_tokens = tokens.clone().expect("Tokens should be present");
-
-            let replacement_tokens = Arc::new(Tokens::new(
-                current_tokens.id_token.to_string(),
-                current_tokens.access_token.to_string(),
-                current_tokens.refresh_token.to_string(),
-                -1000,
-            ));
-            tokens.replace(Arc::clone(&replacement_tokens));
+        let current_tokens = client
+            .tokens
+            .lock()
+            .await
+            .clone()
+            .expect("Tokens should be present");
 
-            replacement_tokens
-        };
+        // Move past the expiry of the issued tokens to force a refresh
+        clock.advance(current_tokens.expires_at - clock.now() + Duration::seconds(1));
 
         let refreshed_tokens = client
             .refresh_tokens_if_needed()
@@ -116,4 +745,56 @@ mod tests {
 
         client.logout().await;
     }
+
+    #[tokio::test]
+    async fn test_concurrent_callers_share_a_single_refresh() {
+        let clock = Arc::new(MockClock::new());
+        let refresher = Arc::new(MockRefresher::new(Arc::clone(&clock)));
+        let client = Client::with_clock("Home Automation", Arc::clone(&clock) as Arc<dyn Clock>)
+            .with_refresher(Arc::clone(&refresher) as Arc<dyn Refresher>);
+
+        let current_tokens = Arc::new(Tokens::new(
+            "id_token".to_string(),
+            "access_token".to_string(),
+            "refresh_token".to_string(),
+            3600,
+            &*clock,
+        ));
+        client
+            .tokens
+            .lock()
+            .await
+            .replace(Arc::clone(&current_tokens));
+
+        client.auth.write().await.replace(
+            HiveAuth::without_user(
+                None,
+                Arc::clone(&client.clock),
+                client.cognito_config.clone(),
+            )
+            .await,
+        );
+
+        clock.advance(Duration::seconds(3601));
+
+        let (first, second, third) = tokio::join!(
+            client.refresh_tokens_if_needed(),
+            client.refresh_tokens_if_needed(),
+            client.refresh_tokens_if_needed(),
+        );
+
+        let first = first.expect("Refresh tokens should succeed");
+
+        assert_eq!(refresher.refreshes(), 1);
+        assert!(!Arc::ptr_eq(&current_tokens, &first));
+        assert_eq!(first.access_token, "access_token_1");
+        assert!(Arc::ptr_eq(
+            &first,
+            &second.expect("Refresh tokens should succeed")
+        ));
+        assert!(Arc::ptr_eq(
+            &first,
+            &third.expect("Refresh tokens should succeed")
+        ));
+    }
 }
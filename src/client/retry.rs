@@ -0,0 +1,83 @@
+use std::sync::atomic::{AtomicU32, Ordering};
+
+/// The default number of retries a [`crate::Client`] can make in a burst, before further
+/// retries must be earned back by successful calls. See [`crate::Client::with_retry_budget`].
+pub const DEFAULT_RETRY_BUDGET: u32 = 10;
+
+/// The number of successful calls needed to earn back a single retry.
+const CALLS_PER_RETRY: u32 = 10;
+
+/// A budget of retries shared across every call made by a [`crate::Client`].
+///
+/// Each retry withdraws from the budget, and each successful call deposits a fraction of a
+/// retry back into it. During a widespread outage the budget quickly runs dry, so calls fail
+/// straight away rather than every concurrent operation retrying against Hive at once.
+#[derive(Debug)]
+pub struct RetryBudget {
+    /// The most the budget can hold, in fractions of a retry.
+    capacity: u32,
+
+    /// The budget currently available, in fractions of a retry.
+    balance: AtomicU32,
+}
+
+impl RetryBudget {
+    pub const fn new(retries: u32) -> Self {
+        let capacity = retries.saturating_mul(CALLS_PER_RETRY);
+
+        Self {
+            capacity,
+            balance: AtomicU32::new(capacity),
+        }
+    }
+
+    /// Withdraw a single retry from the budget, returning whether there was enough budget
+    /// remaining for the retry to be made.
+    pub fn try_withdraw(&self) -> bool {
+        self.balance
+            .fetch_update(Ordering::AcqRel, Ordering::Acquire, |balance| {
+                balance.checked_sub(CALLS_PER_RETRY)
+            })
+            .is_ok()
+    }
+
+    /// Deposit the fraction of a retry earned by a successful call.
+    pub fn deposit(&self) {
+        // The update always succeeds, as it never returns `None`.
+        let _ = self
+            .balance
+            .fetch_update(Ordering::AcqRel, Ordering::Acquire, |balance| {
+                Some(balance.saturating_add(1).min(self.capacity))
+            });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_retries_are_earned_back_by_successful_calls() {
+        let budget = RetryBudget::new(2);
+
+        assert!(budget.try_withdraw());
+        assert!(budget.try_withdraw());
+        assert!(!budget.try_withdraw());
+
+        for _ in 0..CALLS_PER_RETRY - 1 {
+            budget.deposit();
+        }
+        assert!(!budget.try_withdraw());
+
+        budget.deposit();
+        assert!(budget.try_withdraw());
+
+        // The budget never grows beyond its capacity.
+        for _ in 0..CALLS_PER_RETRY * 5 {
+            budget.deposit();
+        }
+        assert!(budget.try_withdraw());
+        assert!(budget.try_withdraw());
+        assert!(!budget.try_withdraw());
+    }
+}
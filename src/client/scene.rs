@@ -0,0 +1,154 @@
+use crate::ApiError;
+use crate::products::{
+    Heating, HeatingStatus, HotWater, Mode, Product, ProductData, ProductType, State, States,
+    ensure_supported_target,
+};
+use serde::{Deserialize, Serialize};
+
+/// A named collection of product states, which can be saved (for example, in configuration)
+/// and applied to a Hive account with [`crate::Client::apply_scene`].
+///
+/// Unlike [Quick Actions](crate::actions::Action), scenes are not stored in the Hive account.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct Scene {
+    /// The states to apply to each product in the scene.
+    pub entries: Vec<SceneEntry>,
+}
+
+/// The states to apply to a single product, as part of a [`Scene`].
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SceneEntry {
+    /// The unique ID of the product.
+    pub product_id: String,
+
+    /// The type of the product.
+    #[serde(rename = "type")]
+    pub r#type: ProductType,
+
+    /// The states to apply to the product.
+    pub states: States,
+}
+
+impl SceneEntry {
+    /// Capture the current states of a product, so that they can be restored later.
+    ///
+    /// Only the states which can be restored are captured - the mode and target temperature,
+    /// and whether Hot Water is on. If the product is boosted, the states it will return to once
+    /// the boost ends are captured instead.
+    ///
+    /// Returns `None` if the product is yet to be mapped by the crate.
+    pub(crate) fn capture(product: &Product<'_>) -> Option<Self> {
+        let (ProductData::Heating(Heating {
+            properties, state, ..
+        })
+        | ProductData::HotWater(HotWater {
+            properties, state, ..
+        })) = &product.data
+        else {
+            return None;
+        };
+
+        let current = match (state.mode(), &properties.previous) {
+            (Some(Mode::Boost), Some(previous)) => previous,
+            _ => state,
+        };
+
+        let mut states = Vec::new();
+
+        if let Some(mode) = current.mode().filter(|mode| !matches!(mode, Mode::Boost)) {
+            states.push(State::Mode(mode.clone()));
+        }
+
+        if let Some(target) = current.target_temperature() {
+            states.push(State::TargetTemperature(target));
+        }
+
+        if let (
+            ProductData::HotWater { .. },
+            Some(status @ (HeatingStatus::On | HeatingStatus::Off)),
+        ) = (&product.data, current.status())
+        {
+            states.push(State::Status(status.clone()));
+        }
+
+        Some(Self {
+            product_id: product.id().to_string(),
+            r#type: product.data.product_type()?,
+            states: States(states),
+        })
+    }
+
+    /// Check the entry can be applied, so that an invalid scene is rejected before any of its
+    /// entries are applied.
+    pub(crate) fn validate(&self) -> Result<(), ApiError> {
+        self.r#type.ensure_applicable(&self.states)?;
+
+        if let Some(celsius) = self.states.target_temperature() {
+            ensure_supported_target(celsius)?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Client;
+    use crate::products::tests::heating;
+
+    #[test]
+    fn test_capturing_a_boosted_product_captures_its_previous_states() {
+        let client = Client::new("Home Automation");
+
+        let product = heating(
+            &client,
+            serde_json::json!({ "online": true, "working": true, "previous": { "mode": "SCHEDULE", "target": 18.0 } }),
+            serde_json::json!({ "mode": "BOOST", "boost": 30, "target": 22.0, "name": "Heating" }),
+        );
+
+        let entry = SceneEntry::capture(&product).expect("Product should be captured");
+
+        assert_eq!(entry.product_id, "heating");
+        assert_eq!(entry.r#type, ProductType::Heating);
+        assert!(matches!(entry.states.mode(), Some(Mode::Schedule)));
+        assert_eq!(entry.states.target_temperature(), Some(18.0));
+        assert!(entry.states.name().is_none());
+        assert!(entry.validate().is_ok());
+
+        assert!(SceneEntry::capture(&Product::new(&client, ProductData::Unknown)).is_none());
+    }
+
+    #[test]
+    fn test_scene_entries_are_validated() {
+        let scene: Scene = serde_json::from_str(
+            r#"{
+                "entries": [
+                    { "product_id": "heating", "type": "heating", "states": { "target": 19.5 } },
+                    { "product_id": "hot-water", "type": "hotwater", "states": { "mode": "OFF" } },
+                    { "product_id": "hot-water", "type": "hotwater", "states": { "target": 19.5 } },
+                    { "product_id": "heating", "type": "heating", "states": { "target": 40.0 } }
+                ]
+            }"#,
+        )
+        .expect("Scene should deserialize");
+
+        assert!(scene.entries[0].validate().is_ok());
+        assert!(scene.entries[1].validate().is_ok());
+        assert!(matches!(
+            scene.entries[2].validate(),
+            Err(ApiError::InvalidStateForProduct { product_type, .. }) if product_type == "hotwater"
+        ));
+        assert!(matches!(
+            scene.entries[3].validate(),
+            Err(ApiError::InvalidTargetTemperature(_))
+        ));
+
+        assert!(
+            serde_json::from_str::<Scene>(
+                r#"{ "entries": [{ "product_id": "light", "type": "warmwhitelight", "states": {} }] }"#
+            )
+            .is_err()
+        );
+    }
+}
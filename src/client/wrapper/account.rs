@@ -0,0 +1,109 @@
+use crate::{Account, ApiError, Client, RefreshError};
+use chrono_tz::Tz;
+
+impl Client {
+    /// Get all of the products, devices and Quick Actions in the Hive account at once.
+    ///
+    /// The three lists are retrieved concurrently, which is considerably faster than retrieving
+    /// each of them in turn.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use hive_client::authentication::{TrustedDevice, User};
+    /// use hive_client::Account;
+    ///
+    /// # tokio_test::block_on(async {
+    /// let client = hive_client::Client::new("Home Automation");
+    ///
+    /// let trusted_device = Some(TrustedDevice::new(
+    ///     "device_password",
+    ///     "device_group_key",
+    ///     "device_key"
+    /// ));
+    ///
+    /// client.login(User::new("example@example.com", "example"), trusted_device)
+    ///     .await
+    ///     .expect("Login should succeed");
+    ///
+    /// let Account { products, devices, actions } = client.get_all()
+    ///     .await
+    ///     .expect("Account should be retrieved");
+    /// # })
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns an error as soon as any one of the lists could not be retrieved - in which case,
+    /// none of the lists are returned, and any requests still in-flight are cancelled.
+    pub async fn get_all(&self) -> Result<Account<'_>, ApiError> {
+        // Refresh the tokens up front, so that the concurrent requests don't all find the same
+        // expired tokens and queue up behind one another to refresh them.
+        self.refresh_tokens_if_needed().await?;
+
+        let (products, devices, actions) =
+            tokio::try_join!(self.get_products(), self.get_devices(), self.get_actions())?;
+
+        Ok(Account {
+            products,
+            devices,
+            actions,
+        })
+    }
+
+    /// Get the time zone of the Hive account.
+    ///
+    /// The slots in a [`crate::products::Schedule`] are times of day local to the Hive account,
+    /// so the time zone can be used to convert between the schedule and UTC - for example,
+    /// with [`crate::products::Schedule::next_change`].
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use chrono::Utc;
+    /// use hive_client::authentication::{TrustedDevice, User};
+    ///
+    /// # tokio_test::block_on(async {
+    /// let client = hive_client::Client::new("Home Automation");
+    ///
+    /// let trusted_device = Some(TrustedDevice::new(
+    ///     "device_password",
+    ///     "device_group_key",
+    ///     "device_key"
+    /// ));
+    ///
+    /// client.login(User::new("example@example.com", "example"), trusted_device)
+    ///     .await
+    ///     .expect("Login should succeed");
+    ///
+    /// let timezone = client.get_account_timezone()
+    ///     .await
+    ///     .expect("Time zone should be retrieved");
+    ///
+    /// println!("The local time of the account is {}", Utc::now().with_timezone(&timezone));
+    /// # })
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the client is not logged in, the user's details could not be
+    /// retrieved from the Hive authentication servers, or the account does not have a
+    /// recognised time zone.
+    pub async fn get_account_timezone(&self) -> Result<Tz, ApiError> {
+        let tokens = self.refresh_tokens_if_needed().await?;
+
+        let timezone = self
+            .auth
+            .read()
+            .await
+            .as_ref()
+            .ok_or(ApiError::RefreshError(RefreshError::NotLoggedIn))?
+            .get_timezone(&tokens)
+            .await?;
+
+        timezone
+            .as_deref()
+            .and_then(|name| name.parse().ok())
+            .ok_or(ApiError::UnknownTimezone(timezone))
+    }
+}
@@ -1,5 +1,5 @@
 use crate::actions::Action;
-use crate::{ApiError, Client};
+use crate::{ApiError, Client, Correlated};
 
 impl Client {
     /// Get all of the [Quick Actions](https://www.hivehome.com/ie/support/Help_Using_Hive/HUH_General/What-are-Quick-Actions) setup in the Hive account.
@@ -41,8 +41,7 @@ impl Client {
     ///
     /// Returns an error if the list of [Quick Actions](https://www.hivehome.com/ie/support/Help_Using_Hive/HUH_General/What-are-Quick-Actions) could not be retrieved.
     pub async fn get_actions(&self) -> Result<Vec<Action<'_>>, ApiError> {
-        self.api
-            .get_actions_data(&*self.refresh_tokens_if_needed().await?)
+        self.with_tokens(|tokens| async move { self.api.get_actions_data(&tokens).await })
             .await
             .map(|actions| {
                 actions
@@ -52,12 +51,72 @@ impl Client {
             })
     }
 
+    /// Get the [Quick Actions](https://www.hivehome.com/ie/support/Help_Using_Hive/HUH_General/What-are-Quick-Actions)
+    /// setup in the Hive account, which were created from a given template.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use hive_client::authentication::{TrustedDevice, User};
+    ///
+    /// # tokio_test::block_on(async {
+    /// let client = hive_client::Client::new("Home Automation");
+    ///
+    /// let trusted_device = Some(TrustedDevice::new(
+    ///     "device_password",
+    ///     "device_group_key",
+    ///     "device_key"
+    /// ));
+    ///
+    /// client.login(User::new("example@example.com", "example"), trusted_device)
+    ///     .await
+    ///     .expect("Login should succeed");
+    ///
+    /// let heating_actions = client.get_actions_by_template("heating")
+    ///     .await
+    ///     .expect("Quick actions should be retrieved");
+    ///
+    /// println!("{:?}", heating_actions);
+    /// # })
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the list of [Quick Actions](https://www.hivehome.com/ie/support/Help_Using_Hive/HUH_General/What-are-Quick-Actions) could not be retrieved.
+    pub async fn get_actions_by_template(
+        &self,
+        template: &str,
+    ) -> Result<Vec<Action<'_>>, ApiError> {
+        self.get_actions().await.map(|actions| {
+            actions
+                .into_iter()
+                .filter(|action| action.data.template == template)
+                .collect()
+        })
+    }
+
     /// Activate a Quick Action by a given ID.
     ///
     /// Wrapped by [`Action::activate`] to activate a returned Quick Action.
     pub(crate) async fn activate_action(&self, action_id: &str) -> Result<bool, ApiError> {
-        self.api
-            .activate_action(&*self.refresh_tokens_if_needed().await?, action_id)
+        self.activate_action_with_id(action_id, None)
             .await
+            .map(|activated| activated.value)
+    }
+
+    /// Activate a Quick Action by a given ID, sending a correlation ID with the request.
+    ///
+    /// Wrapped by [`Action::activate_with_id`] to activate a returned Quick Action.
+    pub(crate) async fn activate_action_with_id(
+        &self,
+        action_id: &str,
+        correlation_id: Option<&str>,
+    ) -> Result<Correlated<bool>, ApiError> {
+        self.with_tokens(|tokens| async move {
+            self.api
+                .activate_action(&tokens, action_id, correlation_id)
+                .await
+        })
+        .await
     }
 }
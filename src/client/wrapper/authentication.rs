@@ -1,10 +1,18 @@
+use crate::helper::target;
 use crate::{
     ApiError, AuthenticationError, Client, RefreshError,
-    authentication::{ChallengeResponse, HiveAuth, Tokens, TrustedDevice, UntrustedDevice, User},
+    authentication::{
+        ChallengeRequest, ChallengeResponse, DeviceConfirmationError, HiveAuth, LoginOutcome,
+        MfaPreferences, SessionExport, Tokens, TrustedDevice, UntrustedDevice, User,
+    },
 };
-use chrono::Utc;
+use chrono::{DateTime, Utc};
 use std::sync::Arc;
 
+/// The most malformed responses to a single challenge which [`Client::login_with`] will ask for
+/// again, before giving up on the login.
+const MAX_MALFORMED_RESPONSES: usize = 3;
+
 impl Client {
     /// Login to Hive as a User.
     ///
@@ -44,7 +52,7 @@ impl Client {
     /// ## Login _without_ a trusted device
     ///
     /// ```no_run
-    /// use hive_client::authentication::{ChallengeResponse, TrustedDevice, User};
+    /// use hive_client::authentication::{ChallengeResponse, LoginOutcome, TrustedDevice, User};
     /// use hive_client::AuthenticationError;
     ///
     /// # tokio_test::block_on(async {
@@ -53,10 +61,13 @@ impl Client {
     /// let attempt = client.login(User::new("example@example.com", "example"), None).await;
     ///
     /// match attempt {
-    ///     Ok(trusted_device) => {
+    ///     Ok(LoginOutcome::NewDevice(trusted_device)) => {
     ///        // Login was successful.
     ///        //
-    ///        // If a trusted device has been returned this can be used to authenticate in the future.
+    ///        // A new trusted device has been confirmed, which can be used to authenticate in the future.
+    ///     },
+    ///     Ok(_) => {
+    ///        // Login was successful, with no new trusted device to persist.
     ///     },
     ///     Err(AuthenticationError::NextChallenge(challenge)) => {
     ///        // Hive prompted for a challenge to be responded to before
@@ -85,17 +96,37 @@ impl Client {
     ///
     /// In the latter case, the caller must generate a [`ChallengeResponse`] and
     /// call [`Client::respond_to_challenge`] to continue with the authentication process.
+    ///
+    /// If the login does not complete within the [login timeout](Client::with_login_timeout),
+    /// the login in progress is discarded and [`AuthenticationError::Timeout`] is returned.
     pub async fn login(
         &self,
         user: User,
         trusted_device: Option<TrustedDevice>,
-    ) -> Result<Option<TrustedDevice>, AuthenticationError> {
+    ) -> Result<LoginOutcome, AuthenticationError> {
+        self.within_login_timeout(Box::pin(self.try_login(user, trusted_device)))
+            .await
+    }
+
+    async fn try_login(
+        &self,
+        user: User,
+        trusted_device: Option<TrustedDevice>,
+    ) -> Result<LoginOutcome, AuthenticationError> {
         let (tokens, untrusted_device) = {
             let mut u = self.user.lock().await;
             let user = u.insert(user);
 
             let mut auth = self.auth.write().await;
-            let auth = auth.insert(HiveAuth::new(user, trusted_device.as_ref()).await);
+            let auth = auth.insert(
+                HiveAuth::new(
+                    user,
+                    trusted_device.as_ref(),
+                    Arc::clone(&self.clock),
+                    self.cognito_config.clone(),
+                )
+                .await,
+            );
 
             auth.login().await?
         };
@@ -104,6 +135,10 @@ impl Client {
         let tokens = lock.insert(Arc::new(tokens));
 
         if let Some(untrusted_device) = untrusted_device {
+            if !self.confirm_new_devices {
+                return Ok(LoginOutcome::UnconfirmedDevice(untrusted_device));
+            }
+
             // We've successfully logged in, and Hive (AWS Cognito) have issued a new device,
             // lets confirm this device so that it is trusted in the future.
             //
@@ -112,13 +147,183 @@ impl Client {
             //    re-authenticate with username/password and 2FA.
             // 2. For future logins (if the trusted device is provided), we can skip the 2FA step
             //    entirely, making for a smoother experience.
-            return Ok(Some(
+            return Ok(LoginOutcome::NewDevice(
                 self.confirm_untrusted_device(untrusted_device, tokens)
                     .await?,
             ));
         }
 
-        Ok(None)
+        if trusted_device.is_some() {
+            Ok(LoginOutcome::ExistingDevice)
+        } else {
+            Ok(LoginOutcome::NoDevice)
+        }
+    }
+
+    /// Authenticate the client with a refresh token, issued by Hive to a previous login.
+    ///
+    /// This skips the interactive login flow (including any Two Factor Authentication challenge)
+    /// entirely, which makes it suitable for headless deployments where there's nobody to respond
+    /// to a [`ChallengeResponse::SmsMfa`] challenge.
+    ///
+    /// The client's configuration (such as its [`Clock`](crate::Clock) or
+    /// [throttling limit](Client::with_max_throttle_wait)) is used to authenticate, so the client
+    /// should be configured first.
+    ///
+    /// If the refresh token was issued to a login using a trusted device, the same device must
+    /// be provided.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use hive_client::authentication::TrustedDevice;
+    ///
+    /// # tokio_test::block_on(async {
+    /// let trusted_device = Some(TrustedDevice::new(
+    ///     "device_password",
+    ///     "device_group_key",
+    ///     "device_key"
+    /// ));
+    ///
+    /// let client = hive_client::Client::new("Home Automation");
+    ///
+    /// client.login_with_refresh_token("refresh_token", trusted_device)
+    ///     .await
+    ///     .expect("Client should be authenticated");
+    ///
+    /// let products = client.get_products()
+    ///     .await
+    ///     .expect("Products should be retrieved");
+    /// # })
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if Hive did not accept the refresh token (or the trusted device).
+    pub async fn login_with_refresh_token(
+        &self,
+        refresh_token: &str,
+        trusted_device: Option<TrustedDevice>,
+    ) -> Result<(), RefreshError> {
+        let auth = HiveAuth::without_user(
+            trusted_device.as_ref(),
+            Arc::clone(&self.clock),
+            self.cognito_config.clone(),
+        )
+        .await;
+
+        // Only the refresh token is needed to mint a new set of tokens, so the rest of the
+        // tokens are left empty (and already expired).
+        let tokens = self
+            .refresher
+            .refresh(
+                &auth,
+                Arc::new(Tokens::new(
+                    String::new(),
+                    String::new(),
+                    refresh_token.to_string(),
+                    0,
+                    &*self.clock,
+                )),
+            )
+            .await?;
+
+        self.auth.write().await.replace(auth);
+        self.tokens.lock().await.replace(Arc::new(tokens));
+
+        log::info!(target: target::AUTH, "Client has been authenticated with a refresh token.");
+
+        Ok(())
+    }
+
+    /// Export a snapshot of the current session (the tokens issued by Hive, and the trusted
+    /// device used to authenticate), so that it can be persisted or handed to another process
+    /// and resumed with [`Client::restore_session`].
+    ///
+    /// Returns `None` if the client is not logged in.
+    ///
+    /// **The export contains live credentials** - see [`SessionExport`] for how it should be
+    /// handled.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use hive_client::authentication::{TrustedDevice, User};
+    ///
+    /// # tokio_test::block_on(async {
+    /// let client = hive_client::Client::new("Home Automation");
+    ///
+    /// let trusted_device = Some(TrustedDevice::new(
+    ///     "device_password",
+    ///     "device_group_key",
+    ///     "device_key"
+    /// ));
+    ///
+    /// client.login(User::new("example@example.com", "example"), trusted_device)
+    ///     .await
+    ///     .expect("Login should succeed");
+    ///
+    /// let session = client.export_session()
+    ///     .await
+    ///     .expect("Client should be logged in");
+    ///
+    /// // Later, or in another process...
+    /// let client = hive_client::Client::new("Home Automation");
+    /// client.restore_session(session).await;
+    /// # })
+    /// ```
+    pub async fn export_session(&self) -> Option<SessionExport> {
+        let tokens = self.tokens.lock().await.clone()?;
+        let trusted_device = self
+            .auth
+            .read()
+            .await
+            .as_ref()
+            .and_then(HiveAuth::trusted_device);
+
+        Some(SessionExport::new(&tokens, trusted_device))
+    }
+
+    /// Resume a session exported by [`Client::export_session`] with the client.
+    ///
+    /// The client's configuration (such as its [`Clock`](crate::Clock) or
+    /// [throttling limit](Client::with_max_throttle_wait)) is used to resume the session, so the
+    /// client should be configured first.
+    ///
+    /// No request is made to Hive until the client is used. If the tokens in the session have
+    /// expired, they are refreshed on first use (which fails if the refresh token has been
+    /// revoked).
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use hive_client::authentication::SessionExport;
+    ///
+    /// # tokio_test::block_on(async {
+    /// # let stored = "";
+    /// let session: SessionExport = serde_json::from_str(stored)
+    ///     .expect("Session should be decoded");
+    ///
+    /// let client = hive_client::Client::new("Home Automation");
+    /// client.restore_session(session).await;
+    ///
+    /// let products = client.get_products()
+    ///     .await
+    ///     .expect("Products should be retrieved");
+    /// # })
+    /// ```
+    pub async fn restore_session(&self, session: SessionExport) {
+        let auth = HiveAuth::without_user(
+            session.trusted_device.as_ref(),
+            Arc::clone(&self.clock),
+            self.cognito_config.clone(),
+        )
+        .await;
+
+        self.auth.write().await.replace(auth);
+        self.tokens.lock().await.replace(Arc::new(session.tokens()));
+
+        log::info!(target: target::AUTH, "Client has been authenticated with an exported session.");
     }
 
     /// Respond to a challenge issued by Hive during the authentication process.
@@ -129,7 +334,7 @@ impl Client {
     /// # Examples
     ///
     /// ```no_run
-    /// use hive_client::authentication::{ChallengeResponse, TrustedDevice, User};
+    /// use hive_client::authentication::{ChallengeResponse, LoginOutcome, TrustedDevice, User};
     /// use hive_client::AuthenticationError;
     ///
     /// # tokio_test::block_on(async {
@@ -138,10 +343,8 @@ impl Client {
     /// let attempt = client.login(User::new("example@example.com", "example"), None).await;
     ///
     /// match attempt {
-    ///     Ok(trusted_device) => {
+    ///     Ok(outcome) => {
     ///         // Login was successful.
-    ///         //
-    ///         // If a trusted device has been returned this can be used to authenticate in the future.
     ///     },
     ///     Err(AuthenticationError::NextChallenge(challenge)) => {
     ///         // Hive prompted for a challenge to be responded to before
@@ -151,10 +354,10 @@ impl Client {
     ///         let sms_code = "123456";
     ///         let response = client.respond_to_challenge(ChallengeResponse::SmsMfa(sms_code.to_string())).await;
     ///
-    ///         if let Ok(trusted_device) = response {
+    ///         if let Ok(LoginOutcome::NewDevice(trusted_device)) = response {
     ///             // Login was successful.
     ///             //
-    ///             // If a trusted device has been returned this can be used to authenticate in the future.
+    ///             // A new trusted device has been confirmed, which can be used to authenticate in the future.
     ///         } else {
     ///             // Challenge failed, respond accordingly.
     ///         }
@@ -170,23 +373,47 @@ impl Client {
     ///
     /// Returns an error if the challenge submission was unsuccessful. If this
     /// happens, the caller must check the error type and handle it accordingly.
+    ///
+    /// Malformed responses (for example, an SMS code which is not six digits) are rejected with
+    /// [`AuthenticationError::InvalidChallengeResponse`] _before_ being sent to Hive.
+    ///
+    /// If the login does not complete within the [login timeout](Client::with_login_timeout),
+    /// the login in progress is discarded and [`AuthenticationError::Timeout`] is returned.
     pub async fn respond_to_challenge(
         &mut self,
         challenge_response: ChallengeResponse,
-    ) -> Result<Option<TrustedDevice>, AuthenticationError> {
-        let (tokens, untrusted_device) = {
+    ) -> Result<LoginOutcome, AuthenticationError> {
+        self.within_login_timeout(Box::pin(self.try_respond_to_challenge(challenge_response)))
+            .await
+    }
+
+    async fn try_respond_to_challenge(
+        &self,
+        challenge_response: ChallengeResponse,
+    ) -> Result<LoginOutcome, AuthenticationError> {
+        if !challenge_response.is_well_formed() {
+            return Err(AuthenticationError::InvalidChallengeResponse);
+        }
+
+        let (tokens, untrusted_device, has_trusted_device) = {
             let auth = self.auth.read().await;
             let auth = auth
                 .as_ref()
                 .ok_or(AuthenticationError::NoAuthenticationInProgress)?;
 
-            auth.respond_to_challenge(challenge_response).await?
+            let (tokens, untrusted_device) = auth.respond_to_challenge(challenge_response).await?;
+
+            (tokens, untrusted_device, auth.has_trusted_device())
         };
 
         let mut lock = self.tokens.lock().await;
         let tokens = lock.insert(Arc::new(tokens));
 
         if let Some(untrusted_device) = untrusted_device {
+            if !self.confirm_new_devices {
+                return Ok(LoginOutcome::UnconfirmedDevice(untrusted_device));
+            }
+
             // We've successfully logged in, and Hive (AWS Cognito) have issued a new device,
             // lets confirm this device so that it is trusted in the future.
             //
@@ -195,13 +422,225 @@ impl Client {
             //    re-authenticate with username/password and 2FA.
             // 2. For future logins (if the trusted device is provided), we can skip the 2FA step
             //    entirely, making for a smoother experience.
-            return Ok(Some(
+            return Ok(LoginOutcome::NewDevice(
                 self.confirm_untrusted_device(untrusted_device, tokens)
                     .await?,
             ));
         }
 
-        Ok(None)
+        if has_trusted_device {
+            Ok(LoginOutcome::ExistingDevice)
+        } else {
+            Ok(LoginOutcome::NoDevice)
+        }
+    }
+
+    /// Login to Hive as a User, responding to any challenges issued by Hive along the way with
+    /// the given callback, until the login completes (or fails).
+    ///
+    /// This collapses the [`Client::login`] and [`Client::respond_to_challenge`] flow into a
+    /// single call, which is convenient for interactive applications - for example, prompting
+    /// the user for an SMS code in a CLI.
+    ///
+    /// If the callback returns a malformed response (for example, an SMS code which is not six
+    /// digits) it is called again with the same challenge, as the response is rejected
+    /// _before_ being sent to Hive - up to three times, after which the login fails.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use hive_client::authentication::{ChallengeRequest, ChallengeResponse, User};
+    ///
+    /// # tokio_test::block_on(async {
+    /// let mut client = hive_client::Client::new("Home Automation");
+    ///
+    /// let outcome = client.login_with(
+    ///     User::new("example@example.com", "example"),
+    ///     None,
+    ///     |challenge| async move {
+    ///         match challenge {
+    ///             ChallengeRequest::SmsMfa { .. } => {
+    ///                 let mut code = String::new();
+    ///                 std::io::stdin().read_line(&mut code).expect("Code should be read");
+    ///
+    ///                 ChallengeResponse::SmsMfa(code.trim().to_string())
+    ///             }
+    ///             challenge => panic!("Unexpected challenge: {challenge:?}"),
+    ///         }
+    ///     },
+    /// )
+    /// .await
+    /// .expect("Login should succeed");
+    /// # })
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the login, or the response to any of the challenges, fails.
+    ///
+    /// Returns [`AuthenticationError::InvalidChallengeResponse`] if the callback keeps returning
+    /// malformed responses to a challenge.
+    pub async fn login_with<F, Fut>(
+        &mut self,
+        user: User,
+        trusted_device: Option<TrustedDevice>,
+        respond: F,
+    ) -> Result<LoginOutcome, AuthenticationError>
+    where
+        F: FnMut(ChallengeRequest) -> Fut,
+        Fut: Future<Output = ChallengeResponse>,
+    {
+        let attempt = self.login(user, trusted_device).await;
+
+        self.respond_until_complete(attempt, respond).await
+    }
+
+    /// Respond to each challenge issued during a login with the given callback, until the login
+    /// completes (or fails).
+    async fn respond_until_complete<F, Fut>(
+        &mut self,
+        mut attempt: Result<LoginOutcome, AuthenticationError>,
+        mut respond: F,
+    ) -> Result<LoginOutcome, AuthenticationError>
+    where
+        F: FnMut(ChallengeRequest) -> Fut,
+        Fut: Future<Output = ChallengeResponse>,
+    {
+        while let Err(AuthenticationError::NextChallenge(challenge)) = attempt {
+            let mut malformed = 0;
+
+            attempt = loop {
+                match self
+                    .respond_to_challenge(respond(challenge.clone()).await)
+                    .await
+                {
+                    Err(AuthenticationError::InvalidChallengeResponse)
+                        if malformed + 1 < MAX_MALFORMED_RESPONSES =>
+                    {
+                        malformed += 1;
+
+                        log::warn!(target: target::AUTH, "Challenge response was malformed, asking for it again.");
+                    }
+                    attempt => break attempt,
+                }
+            };
+        }
+
+        attempt
+    }
+
+    /// Get the Multi-Factor Authentication (MFA) settings of the logged in user.
+    ///
+    /// This can be used to know ahead of time whether a future login (without a trusted device)
+    /// should expect to be issued a challenge, such as [`ChallengeResponse::SmsMfa`].
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use hive_client::authentication::{TrustedDevice, User};
+    ///
+    /// # tokio_test::block_on(async {
+    /// let client = hive_client::Client::new("Home Automation");
+    ///
+    /// let trusted_device = Some(TrustedDevice::new(
+    ///     "device_password",
+    ///     "device_group_key",
+    ///     "device_key"
+    /// ));
+    ///
+    /// client.login(User::new("example@example.com", "example"), trusted_device)
+    ///     .await
+    ///     .expect("Login should succeed");
+    ///
+    /// let preferences = client.get_mfa_preferences()
+    ///     .await
+    ///     .expect("MFA preferences should be retrieved");
+    ///
+    /// if preferences.is_enabled() {
+    ///     println!("MFA is enabled, preferring: {:?}", preferences.preferred);
+    /// }
+    /// # })
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the client is not logged in, or the user's details could not be
+    /// retrieved from the Hive authentication servers.
+    pub async fn get_mfa_preferences(&self) -> Result<MfaPreferences, ApiError> {
+        let tokens = self.refresh_tokens_if_needed().await?;
+
+        Ok(self
+            .auth
+            .read()
+            .await
+            .as_ref()
+            .ok_or(ApiError::RefreshError(RefreshError::NotLoggedIn))?
+            .get_mfa_preferences(&tokens)
+            .await?)
+    }
+
+    /// The challenges which logging in as the user should be expected to issue, so that the
+    /// prompts for them can be prepared up front (for example, in a setup wizard).
+    ///
+    /// Hive only reveals the MFA settings of an account to the user themselves, so the client
+    /// must already be logged in as the user. No challenges are expected if the client is
+    /// using a trusted device, as Hive skips MFA for remembered devices.
+    ///
+    /// See [`MfaPreferences::expected_challenges`].
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use hive_client::authentication::{ChallengeRequest, TrustedDevice, User};
+    ///
+    /// # tokio_test::block_on(async {
+    /// let client = hive_client::Client::new("Home Automation");
+    ///
+    /// client.login(User::new("example@example.com", "example"), None)
+    ///     .await
+    ///     .expect("Login should succeed");
+    ///
+    /// let challenges = client.expected_challenges(&User::new("example@example.com", "example"))
+    ///     .await
+    ///     .expect("Expected challenges should be retrieved");
+    ///
+    /// if challenges.iter().any(|challenge| matches!(challenge, ChallengeRequest::SmsMfa { .. })) {
+    ///     println!("Logging in will require a SMS code");
+    /// }
+    /// # })
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the client is not logged in as the user, or the user's details could
+    /// not be retrieved from the Hive authentication servers.
+    pub async fn expected_challenges(
+        &self,
+        user: &User,
+    ) -> Result<Vec<ChallengeRequest>, ApiError> {
+        let is_logged_in_user = self
+            .user
+            .lock()
+            .await
+            .as_ref()
+            .is_some_and(|logged_in_user| logged_in_user.username == user.username);
+
+        if !is_logged_in_user {
+            return Err(ApiError::RefreshError(RefreshError::NotLoggedIn));
+        }
+
+        let has_trusted_device = self
+            .auth
+            .read()
+            .await
+            .as_ref()
+            .is_some_and(HiveAuth::has_trusted_device);
+
+        if has_trusted_device {
+            return Ok(vec![]);
+        }
+
+        Ok(self.get_mfa_preferences().await?.expected_challenges())
     }
 
     /// Logout from Hive.
@@ -245,7 +684,145 @@ impl Client {
         drop(self.user.lock().await.take());
         drop(self.tokens.lock().await.take());
 
-        log::info!("Logout is complete, tokens have been dropped.");
+        log::info!(target: target::AUTH, "Logout is complete, tokens have been dropped.");
+    }
+
+    /// Cancel a login which is still in progress (for example, one waiting on a
+    /// [`ChallengeResponse`]), so that the next call to [`Client::login`] starts afresh.
+    ///
+    /// If the login has already completed, this does nothing - use [`Client::logout`] instead.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use hive_client::authentication::User;
+    /// use hive_client::AuthenticationError;
+    ///
+    /// # tokio_test::block_on(async {
+    /// let mut client = hive_client::Client::new("Home Automation");
+    ///
+    /// let attempt = client.login(User::new("example@example.com", "example"), None).await;
+    ///
+    /// if let Err(AuthenticationError::NextChallenge(_)) = attempt {
+    ///     // The user abandoned the challenge, so discard the login in progress.
+    ///     client.cancel_login().await;
+    /// }
+    /// # })
+    /// ```
+    pub async fn cancel_login(&mut self) {
+        if self.tokens.lock().await.is_some() {
+            return;
+        }
+
+        drop(self.auth.write().await.take());
+        drop(self.user.lock().await.take());
+
+        log::info!(target: target::AUTH, "Login has been cancelled, the login in progress has been dropped.");
+    }
+
+    /// Run a step of the login flow, discarding the login in progress (including any tokens it
+    /// has already been issued) if it does not complete within the login timeout.
+    async fn within_login_timeout(
+        &self,
+        step: impl Future<Output = Result<LoginOutcome, AuthenticationError>>,
+    ) -> Result<LoginOutcome, AuthenticationError> {
+        if let Ok(outcome) = tokio::time::timeout(self.login_timeout, step).await {
+            return outcome;
+        }
+
+        drop(self.auth.write().await.take());
+        drop(self.user.lock().await.take());
+        drop(self.tokens.lock().await.take());
+
+        log::error!(target: target::AUTH,
+            "Login did not complete within {:?}, the login in progress has been dropped.",
+            self.login_timeout
+        );
+
+        Err(AuthenticationError::Timeout(self.login_timeout))
+    }
+
+    /// Whether the authentication tokens issued by Hive will have expired within the given
+    /// window of time from now.
+    ///
+    /// The client refreshes its tokens automatically (shortly before they expire), so this is
+    /// only needed when building a custom refresh policy - for example, to schedule a call to
+    /// Hive ahead of the tokens expiring. If the client is not logged in, the tokens are always
+    /// considered to have expired.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use hive_client::authentication::{TrustedDevice, User};
+    /// use std::time::Duration;
+    ///
+    /// # tokio_test::block_on(async {
+    /// let client = hive_client::Client::new("Home Automation");
+    ///
+    /// let trusted_device = Some(TrustedDevice::new(
+    ///     "device_password",
+    ///     "device_group_key",
+    ///     "device_key"
+    /// ));
+    ///
+    /// client.login(User::new("example@example.com", "example"), trusted_device)
+    ///     .await
+    ///     .expect("Login should succeed");
+    ///
+    /// if client.tokens_expire_within(Duration::from_secs(5 * 60)).await {
+    ///     println!("The tokens will be refreshed by the next call to Hive");
+    /// }
+    /// # })
+    /// ```
+    pub async fn tokens_expire_within(&self, window: std::time::Duration) -> bool {
+        let window = chrono::Duration::from_std(window).unwrap_or(chrono::Duration::MAX);
+
+        self.tokens
+            .lock()
+            .await
+            .as_ref()
+            .is_none_or(|tokens| tokens.expires_within(window, &*self.clock))
+    }
+
+    /// Refresh the authentication tokens issued by Hive now, regardless of whether they have
+    /// expired, returning the date and time the new tokens expire at.
+    ///
+    /// The client refreshes its tokens automatically, so this is only needed to take direct
+    /// control of the session - for example, to refresh ahead of a long-running operation.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use hive_client::authentication::{TrustedDevice, User};
+    ///
+    /// # tokio_test::block_on(async {
+    /// let client = hive_client::Client::new("Home Automation");
+    ///
+    /// let trusted_device = Some(TrustedDevice::new(
+    ///     "device_password",
+    ///     "device_group_key",
+    ///     "device_key"
+    /// ));
+    ///
+    /// client.login(User::new("example@example.com", "example"), trusted_device)
+    ///     .await
+    ///     .expect("Login should succeed");
+    ///
+    /// let expires_at = client.refresh_now()
+    ///     .await
+    ///     .expect("Tokens should be refreshed");
+    ///
+    /// println!("The tokens now expire at {expires_at}");
+    /// # })
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the client is not logged in, or the tokens could not be refreshed.
+    pub async fn refresh_now(&self) -> Result<DateTime<Utc>, ApiError> {
+        self.refresh_tokens_where(|_| true)
+            .await
+            .map(|tokens| tokens.expires_at)
     }
 
     /// Refresh the currently stored [`Tokens`], if they have expired.
@@ -253,11 +830,68 @@ impl Client {
     /// This is commonly used by wrapper API methods, before performing a call to
     /// the Hive API, to ensure their tokens are fresh and ready to be used.
     pub(crate) async fn refresh_tokens_if_needed(&self) -> Result<Arc<Tokens>, ApiError> {
+        self.refresh_tokens_where(|_| false).await
+    }
+
+    /// Perform a call to the Hive API with the currently stored [`Tokens`], refreshing them
+    /// first if they have expired.
+    ///
+    /// If the Hive API rejects the tokens (for example, because they expired while the request
+    /// was in-flight) the tokens are refreshed and the call is retried, once - provided the
+    /// client's retry budget has not run dry.
+    pub(crate) async fn with_tokens<T, F, Fut>(&self, call: F) -> Result<T, ApiError>
+    where
+        F: Fn(Arc<Tokens>) -> Fut,
+        Fut: Future<Output = Result<T, ApiError>>,
+    {
+        let tokens = self.refresh_tokens_if_needed().await?;
+
+        let result = match call(Arc::clone(&tokens)).await {
+            Err(ApiError::Unauthorized(status)) if self.retry_budget.try_withdraw() => {
+                log::warn!(target: target::AUTH, "Tokens were rejected by the Hive API ({status}), retrying once.");
+
+                // Only refresh if another call hasn't already replaced the rejected tokens.
+                let tokens = self
+                    .refresh_tokens_where(|current_tokens| Arc::ptr_eq(current_tokens, &tokens))
+                    .await?;
+
+                call(tokens).await
+            }
+            Err(ApiError::Unauthorized(status)) => {
+                log::warn!(target: target::AUTH,
+                    "Tokens were rejected by the Hive API ({status}), but the retry budget is exhausted."
+                );
+
+                Err(ApiError::Unauthorized(status))
+            }
+            result => result,
+        };
+
+        if result.is_ok() {
+            self.retry_budget.deposit();
+        }
+
+        result
+    }
+
+    /// Refresh the currently stored [`Tokens`], if they have expired, or if they match
+    /// the given predicate.
+    ///
+    /// The lock on the stored tokens is held for the duration of the refresh, so concurrent
+    /// callers share a single refresh - those waiting on the lock will find the replacement
+    /// tokens are no longer expired, and return them without refreshing again.
+    async fn refresh_tokens_where(
+        &self,
+        should_refresh: impl FnOnce(&Arc<Tokens>) -> bool,
+    ) -> Result<Arc<Tokens>, ApiError> {
         let mut token_to_refresh = self.tokens.lock().await;
 
         match token_to_refresh.as_ref() {
             mut current_tokens
-                if current_tokens.is_some_and(|tokens| tokens.expires_at <= Utc::now()) =>
+                if current_tokens.is_some_and(|tokens| {
+                    tokens.expires_within(Tokens::REFRESH_SKEW, &*self.clock)
+                        || should_refresh(tokens)
+                }) =>
             {
                 let auth = self.auth.read().await;
                 let auth = auth
@@ -268,7 +902,8 @@ impl Client {
                     .expect("Tokens must already be present to need to refresh");
 
                 let replacement_tokens = Arc::new(
-                    auth.refresh_tokens(Arc::clone(current_tokens))
+                    self.refresher
+                        .refresh(auth, Arc::clone(current_tokens))
                         .await
                         .map_err(ApiError::RefreshError)?,
                 );
@@ -277,7 +912,7 @@ impl Client {
 
                 drop(token_to_refresh);
 
-                log::info!(
+                log::info!(target: target::AUTH,
                     "Tokens have been refreshed successfully. New expiration time: {}",
                     replacement_tokens.expires_at,
                 );
@@ -289,6 +924,167 @@ impl Client {
         }
     }
 
+    /// Confirm the trusted device currently being used by the client again, generating a new
+    /// device password for its existing device key.
+    ///
+    /// This is a targeted recovery path for when the password of a trusted device is no longer
+    /// accepted by Hive (for example, the device challenge fails with an invalid device key
+    /// or password), without starting a new login. The client uses the new credentials
+    /// straight away, and they should be stored in place of the previous trusted device.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use hive_client::authentication::{TrustedDevice, User};
+    ///
+    /// # tokio_test::block_on(async {
+    /// let client = hive_client::Client::new("Home Automation");
+    ///
+    /// let trusted_device = Some(TrustedDevice::new(
+    ///     "device_password",
+    ///     "device_group_key",
+    ///     "device_key"
+    /// ));
+    ///
+    /// client.login(User::new("example@example.com", "example"), trusted_device)
+    ///     .await
+    ///     .expect("Login should succeed");
+    ///
+    /// let trusted_device = client.reconfirm_device()
+    ///     .await
+    ///     .expect("Device should be confirmed again");
+    /// # })
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the client is not logged in, is not using a trusted device, or the
+    /// device could not be confirmed.
+    pub async fn reconfirm_device(&self) -> Result<TrustedDevice, AuthenticationError> {
+        let tokens = self
+            .tokens
+            .lock()
+            .await
+            .clone()
+            .ok_or(AuthenticationError::NoAuthenticationInProgress)?;
+
+        let device_keys = self
+            .auth
+            .read()
+            .await
+            .as_ref()
+            .ok_or(AuthenticationError::NoAuthenticationInProgress)?
+            .device_keys()
+            .ok_or(DeviceConfirmationError::NoTrustedDevice)?;
+
+        self.confirm_untrusted_device(device_keys, &tokens).await
+    }
+
+    /// Confirm a new device issued by Hive during login, which was left unconfirmed because
+    /// the client is configured not to [confirm new devices](Client::confirm_new_devices).
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use hive_client::authentication::{LoginOutcome, TrustedDevice, User};
+    ///
+    /// # tokio_test::block_on(async {
+    /// let client = hive_client::Client::new("Home Automation")
+    ///     .confirm_new_devices(false);
+    ///
+    /// let outcome = client.login(User::new("example@example.com", "example"), None)
+    ///     .await
+    ///     .expect("Login should succeed");
+    ///
+    /// if let LoginOutcome::UnconfirmedDevice(untrusted_device) = outcome {
+    ///     // The user chose to trust this device after all.
+    ///     let trusted_device = client.confirm_device(untrusted_device)
+    ///         .await
+    ///         .expect("Device should be confirmed");
+    /// }
+    /// # })
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the client is not logged in, or the device could not be confirmed.
+    pub async fn confirm_device(
+        &self,
+        untrusted_device: UntrustedDevice,
+    ) -> Result<TrustedDevice, AuthenticationError> {
+        let tokens = self
+            .tokens
+            .lock()
+            .await
+            .clone()
+            .ok_or(AuthenticationError::NoAuthenticationInProgress)?;
+
+        self.confirm_untrusted_device(untrusted_device, &tokens)
+            .await
+    }
+
+    /// Forget the trusted devices remembered by Hive which have not been used to authenticate
+    /// within the given window, returning the keys of the devices which were forgotten.
+    ///
+    /// Hive remembers every device confirmed on the account, so accounts which log in from
+    /// many places tend to accumulate devices which are no longer in use. The trusted device
+    /// currently being used by the client is never forgotten.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use hive_client::authentication::{TrustedDevice, User};
+    /// use std::time::Duration;
+    ///
+    /// # tokio_test::block_on(async {
+    /// let client = hive_client::Client::new("Home Automation");
+    ///
+    /// let trusted_device = Some(TrustedDevice::new(
+    ///     "device_password",
+    ///     "device_group_key",
+    ///     "device_key"
+    /// ));
+    ///
+    /// client.login(User::new("example@example.com", "example"), trusted_device)
+    ///     .await
+    ///     .expect("Login should succeed");
+    ///
+    /// // Forget any devices which haven't been used in the last 90 days.
+    /// let forgotten = client.prune_devices(Duration::from_secs(90 * 24 * 60 * 60))
+    ///     .await
+    ///     .expect("Stale devices should be forgotten");
+    ///
+    /// println!("Forgot {} devices", forgotten.len());
+    /// # })
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the client is not logged in, or the devices could not be listed.
+    ///
+    /// If only some of the stale devices could be forgotten, an
+    /// [`AuthenticationError::PruneIncomplete`] is returned, reporting which devices were
+    /// forgotten and which were not.
+    pub async fn prune_devices(
+        &self,
+        older_than: std::time::Duration,
+    ) -> Result<Vec<String>, AuthenticationError> {
+        let tokens = self
+            .tokens
+            .lock()
+            .await
+            .clone()
+            .ok_or(AuthenticationError::NoAuthenticationInProgress)?;
+
+        self.auth
+            .read()
+            .await
+            .as_ref()
+            .ok_or(AuthenticationError::NoAuthenticationInProgress)?
+            .prune_devices(&tokens, older_than)
+            .await
+    }
+
     /// Confirm an untrusted device issued by Hive (AWS Cognito) during the authentication
     /// process.
     ///
@@ -319,3 +1115,60 @@ impl Client {
         Ok(trusted_device)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    #[tokio::test(start_paused = true)]
+    async fn test_logins_which_time_out_are_discarded() {
+        let client = Client::new("Home Automation").with_login_timeout(Duration::from_secs(5));
+        let user = User::new("example@example.com", "example");
+
+        client.auth.write().await.replace(
+            HiveAuth::new(
+                &user,
+                None,
+                Arc::clone(&client.clock),
+                client.cognito_config.clone(),
+            )
+            .await,
+        );
+        client.user.lock().await.replace(user);
+
+        assert!(matches!(
+            client
+                .within_login_timeout(std::future::pending())
+                .await,
+            Err(AuthenticationError::Timeout(timeout)) if timeout == Duration::from_secs(5)
+        ));
+        assert!(client.auth.read().await.is_none());
+        assert!(client.user.lock().await.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_malformed_challenge_responses_are_only_asked_for_again_a_few_times() {
+        let mut client = Client::new("Home Automation");
+        let mut asked = 0;
+
+        assert!(matches!(
+            client
+                .respond_until_complete(
+                    Err(AuthenticationError::NextChallenge(
+                        ChallengeRequest::SmsMfa {
+                            attempts_remaining: None,
+                        }
+                    )),
+                    |_| {
+                        asked += 1;
+
+                        async { ChallengeResponse::SmsMfa(String::new()) }
+                    },
+                )
+                .await,
+            Err(AuthenticationError::InvalidChallengeResponse)
+        ));
+        assert_eq!(asked, MAX_MALFORMED_RESPONSES);
+    }
+}
@@ -0,0 +1,88 @@
+use crate::products::Product;
+use crate::{ApiError, Client};
+use tokio_util::sync::CancellationToken;
+
+impl Client {
+    /// Get the products currently running in the Hive account, unless the call is cancelled
+    /// first.
+    ///
+    /// See [`Client::get_products`].
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use hive_client::authentication::{TrustedDevice, User};
+    /// use tokio_util::sync::CancellationToken;
+    ///
+    /// # tokio_test::block_on(async {
+    /// let client = hive_client::Client::new("Home Automation");
+    ///
+    /// let trusted_device = Some(TrustedDevice::new(
+    ///     "device_password",
+    ///     "device_group_key",
+    ///     "device_key"
+    /// ));
+    ///
+    /// client.login(User::new("example@example.com", "example"), trusted_device)
+    ///     .await
+    ///     .expect("Login should succeed");
+    ///
+    /// let cancel = CancellationToken::new();
+    ///
+    /// // For example, cancel the call when the view showing the products is closed.
+    /// let products = client.get_products_with_cancel(&cancel).await;
+    /// # })
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ApiError::Cancelled`] if the call was cancelled, otherwise the same errors as
+    /// [`Client::get_products`].
+    pub async fn get_products_with_cancel(
+        &self,
+        cancel: &CancellationToken,
+    ) -> Result<Vec<Product<'_>>, ApiError> {
+        Box::pin(self.cancellable(cancel, self.get_products())).await
+    }
+
+    /// Run a call to the Hive API, unless it is cancelled first.
+    ///
+    /// Any refresh of the tokens needed by the call is allowed to finish before the call can be
+    /// cancelled, so that cancelling never leaves the client part-way through a refresh.
+    pub(crate) async fn cancellable<T>(
+        &self,
+        cancel: &CancellationToken,
+        call: impl Future<Output = Result<T, ApiError>>,
+    ) -> Result<T, ApiError> {
+        if cancel.is_cancelled() {
+            return Err(ApiError::Cancelled);
+        }
+
+        self.refresh_tokens_if_needed().await?;
+
+        tokio::select! {
+            biased;
+
+            () = cancel.cancelled() => Err(ApiError::Cancelled),
+            result = call => result,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_cancelled_calls_are_not_sent() {
+        let client = Client::new("Home Automation");
+        let cancel = CancellationToken::new();
+
+        cancel.cancel();
+
+        assert!(matches!(
+            client.get_products_with_cancel(&cancel).await,
+            Err(ApiError::Cancelled)
+        ));
+    }
+}
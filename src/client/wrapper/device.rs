@@ -1,5 +1,7 @@
-use crate::devices::Device;
+use crate::devices::{Device, DeviceData};
 use crate::{ApiError, Client};
+use serde::Deserialize;
+use serde_json::Value;
 
 impl Client {
     /// Get all of the devices associated with the Hive account.
@@ -37,11 +39,115 @@ impl Client {
     ///
     /// # Errors
     ///
-    /// Returns an error if the list of devices could not be retrieved.
+    /// Returns an error if the list of devices could not be retrieved, or if the client was
+    /// configured to [fail on unknown](Client::fail_on_unknown) devices and one was returned.
     pub async fn get_devices(&self) -> Result<Vec<Device>, ApiError> {
-        self.api
-            .get_devices(&*self.refresh_tokens_if_needed().await?)
+        if self.fail_on_unknown {
+            return self
+                .with_tokens(|tokens| async move { self.api.get_devices::<Value>(&tokens).await })
+                .await?
+                .into_iter()
+                .map(|raw| Ok(Device::new(DeviceData::decode_known(raw)?)))
+                .collect();
+        }
+
+        self.with_tokens(|tokens| async move { self.api.get_devices(&tokens).await })
             .await
             .map(|data| data.into_iter().map(Device::new).collect())
     }
+
+    /// Get the devices of a particular type associated with the Hive account, such as
+    /// [`crate::devices::Thermostat`] or [`crate::devices::Hub`].
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use hive_client::authentication::{TrustedDevice, User};
+    /// use hive_client::devices::Thermostat;
+    ///
+    /// # tokio_test::block_on(async {
+    /// let client = hive_client::Client::new("Home Automation");
+    ///
+    /// let trusted_device = Some(TrustedDevice::new(
+    ///     "device_password",
+    ///     "device_group_key",
+    ///     "device_key"
+    /// ));
+    ///
+    /// client.login(User::new("example@example.com", "example"), trusted_device)
+    ///     .await
+    ///     .expect("Login should succeed");
+    ///
+    /// let thermostats = client.get_devices_by_type::<Thermostat>()
+    ///     .await
+    ///     .expect("Thermostats should be retrieved");
+    ///
+    /// for thermostat in thermostats {
+    ///     println!("{}: {:?}%", thermostat.state.name, thermostat.properties.battery_percentage);
+    /// }
+    /// # })
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the list of devices could not be retrieved.
+    pub async fn get_devices_by_type<T: TryFrom<DeviceData>>(&self) -> Result<Vec<T>, ApiError> {
+        Ok(self
+            .get_devices()
+            .await?
+            .into_iter()
+            .filter_map(|device| T::try_from(device.data).ok())
+            .collect())
+    }
+
+    /// Get the raw data of any devices in the Hive account which are yet to be mapped by
+    /// the crate (those which would be returned as [`DeviceData::Unknown`]).
+    ///
+    /// This is intended to help with contributing support for new devices - the data returned
+    /// can be shared in an issue, as a fixture for the new device.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use hive_client::authentication::{TrustedDevice, User};
+    ///
+    /// # tokio_test::block_on(async {
+    /// let client = hive_client::Client::new("Home Automation");
+    ///
+    /// let trusted_device = Some(TrustedDevice::new(
+    ///     "device_password",
+    ///     "device_group_key",
+    ///     "device_key"
+    /// ));
+    ///
+    /// client.login(User::new("example@example.com", "example"), trusted_device)
+    ///     .await
+    ///     .expect("Login should succeed");
+    ///
+    /// for device in client.list_unknown_devices().await.expect("Devices should be retrieved") {
+    ///     println!("{device:#}");
+    /// }
+    /// # })
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the list of devices could not be retrieved.
+    pub async fn list_unknown_devices(&self) -> Result<Vec<Value>, ApiError> {
+        self.with_tokens(|tokens| async move { self.api.get_devices::<Value>(&tokens).await })
+            .await
+            .map(|devices| {
+                devices
+                    .into_iter()
+                    .filter(|device| {
+                        // Devices which can't be decoded at all are just as unmapped as those
+                        // which are decoded as unknown.
+                        matches!(
+                            DeviceData::deserialize(device),
+                            Err(_) | Ok(DeviceData::Unknown)
+                        )
+                    })
+                    .collect()
+            })
+    }
 }
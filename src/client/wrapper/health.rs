@@ -0,0 +1,79 @@
+use crate::{ApiError, Client};
+
+impl Client {
+    /// Check that the Hive account is authenticated and reachable.
+    ///
+    /// This refreshes the authentication tokens (if needed) and makes a lightweight request to
+    /// the Hive API, which makes it suitable for use in liveness or readiness checks.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use hive_client::authentication::{TrustedDevice, User};
+    ///
+    /// # tokio_test::block_on(async {
+    /// let client = hive_client::Client::new("Home Automation");
+    ///
+    /// let trusted_device = Some(TrustedDevice::new(
+    ///     "device_password",
+    ///     "device_group_key",
+    ///     "device_key"
+    /// ));
+    ///
+    /// client.login(User::new("example@example.com", "example"), trusted_device)
+    ///     .await
+    ///     .expect("Login should succeed");
+    ///
+    /// let is_healthy = client.health_check().await.is_ok();
+    /// # })
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the client is not logged in, the tokens could not be refreshed, or
+    /// the Hive API did not return a successful response.
+    pub async fn health_check(&self) -> Result<(), ApiError> {
+        self.with_tokens(|tokens| async move { self.api.health_check(&tokens).await })
+            .await
+    }
+
+    /// Open a connection to the Hive API ahead of time, so that the first request made by the
+    /// client doesn't pay the cost of establishing the connection.
+    ///
+    /// This is entirely optional, and is most useful for interactive applications, where it can
+    /// be called straight after logging in (or even concurrently with logging in).
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use hive_client::authentication::{TrustedDevice, User};
+    ///
+    /// # tokio_test::block_on(async {
+    /// let client = hive_client::Client::new("Home Automation");
+    ///
+    /// let trusted_device = Some(TrustedDevice::new(
+    ///     "device_password",
+    ///     "device_group_key",
+    ///     "device_key"
+    /// ));
+    ///
+    /// let (login, _) = tokio::join!(
+    ///     client.login(User::new("example@example.com", "example"), trusted_device),
+    ///     client.warm_up(),
+    /// );
+    ///
+    /// login.expect("Login should succeed");
+    ///
+    /// let products = client.get_products()
+    ///     .await
+    ///     .expect("Products should be retrieved");
+    /// # })
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a connection to the Hive API could not be established.
+    pub async fn warm_up(&self) -> Result<(), ApiError> {
+        self.api.warm_up().await
+    }
+}
@@ -1,8 +1,14 @@
 //! External Client methods which are part of the public API and can be used directly by a caller
 //! to interact with the Hive API.
 
+mod account;
 mod action;
 mod authentication;
+mod cancel;
 mod device;
+mod health;
 mod product;
+mod raw;
+mod scene;
+mod subscribe;
 mod weather;
@@ -1,11 +1,23 @@
-use crate::products::{Product, States};
-use crate::{ApiError, Client};
+use crate::helper::target;
+use crate::products::{
+    HeatingZone, Product, ProductData, ProductType, State, States, UsageSeries,
+    ensure_supported_target,
+};
+use crate::{ApiError, Client, Correlated};
+use chrono::{DateTime, Utc};
+use futures_util::future::join_all;
+use serde::Deserialize;
+use serde_json::Value;
+use std::ops::Range;
 
 impl Client {
     /// Get all of the Hive products setup in the Hive account.
     ///
     /// For example, the Heating or Hot Water products.
     ///
+    /// Products which are disabled in the Hive account are left out, unless the client is
+    /// configured to [include disabled products](Client::include_disabled_products).
+    ///
     /// # Examples
     ///
     /// ```no_run
@@ -37,19 +49,228 @@ impl Client {
     ///
     /// # Errors
     ///
-    /// Returns an error if the list of products could not be retrieved.
+    /// Returns an error if the list of products could not be retrieved, or if the client was
+    /// configured to [fail on unknown](Client::fail_on_unknown) products and one was returned.
     pub async fn get_products(&self) -> Result<Vec<Product<'_>>, ApiError> {
-        self.api
-            .get_product_data(&*self.refresh_tokens_if_needed().await?)
+        let products: Vec<ProductData> = if self.fail_on_unknown {
+            self.with_tokens(
+                |tokens| async move { self.api.get_product_data::<Value>(&tokens).await },
+            )
+            .await?
+            .into_iter()
+            .map(ProductData::decode_known)
+            .collect::<Result<_, _>>()?
+        } else {
+            self.with_tokens(|tokens| async move { self.api.get_product_data(&tokens).await })
+                .await?
+        };
+
+        Ok(products
+            .into_iter()
+            .filter(|data| self.include_disabled_products || data.is_enabled())
+            .map(|data| Product::new(self, data))
+            .collect())
+    }
+
+    /// Get the raw data of any products in the Hive account which are yet to be mapped by
+    /// the crate (those which would be returned as [`ProductData::Unknown`]).
+    ///
+    /// This is intended to help with contributing support for new products - the data returned
+    /// can be shared in an issue, as a fixture for the new product.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use hive_client::authentication::{TrustedDevice, User};
+    ///
+    /// # tokio_test::block_on(async {
+    /// let client = hive_client::Client::new("Home Automation");
+    ///
+    /// let trusted_device = Some(TrustedDevice::new(
+    ///     "device_password",
+    ///     "device_group_key",
+    ///     "device_key"
+    /// ));
+    ///
+    /// client.login(User::new("example@example.com", "example"), trusted_device)
+    ///     .await
+    ///     .expect("Login should succeed");
+    ///
+    /// for product in client.list_unknown_products().await.expect("Products should be retrieved") {
+    ///     println!("{product:#}");
+    /// }
+    /// # })
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the list of products could not be retrieved.
+    pub async fn list_unknown_products(&self) -> Result<Vec<Value>, ApiError> {
+        self.with_tokens(|tokens| async move { self.api.get_product_data::<Value>(&tokens).await })
             .await
             .map(|products| {
                 products
                     .into_iter()
-                    .map(|data| Product::new(self, data))
+                    .filter(|product| {
+                        // Products which can't be decoded at all are just as unmapped as those
+                        // which are decoded as unknown.
+                        matches!(
+                            ProductData::deserialize(product),
+                            Err(_) | Ok(ProductData::Unknown)
+                        )
+                    })
                     .collect()
             })
     }
 
+    /// Get the products currently running in the Hive account, with the name of each product's
+    /// zone filled in from the devices in the account.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use hive_client::authentication::{TrustedDevice, User};
+    /// use hive_client::products::{Heating, ProductData};
+    ///
+    /// # tokio_test::block_on(async {
+    /// let client = hive_client::Client::new("Home Automation");
+    ///
+    /// let trusted_device = Some(TrustedDevice::new(
+    ///     "device_password",
+    ///     "device_group_key",
+    ///     "device_key"
+    /// ));
+    ///
+    /// client.login(User::new("example@example.com", "example"), trusted_device)
+    ///     .await
+    ///     .expect("Login should succeed");
+    ///
+    /// let products = client.get_products_with_zones()
+    ///     .await
+    ///     .expect("Products should be retrieved");
+    ///
+    /// for product in &products {
+    ///     if let ProductData::Heating(Heating { properties, .. }) = &product.data {
+    ///         println!("{} is in {:?}", product.data, properties.zone_name);
+    ///     }
+    /// }
+    /// # })
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if either the list of products or the list of devices could not
+    /// be retrieved.
+    pub async fn get_products_with_zones(&self) -> Result<Vec<Product<'_>>, ApiError> {
+        self.refresh_tokens_if_needed().await?;
+
+        let (mut products, devices) = tokio::try_join!(self.get_products(), self.get_devices())?;
+
+        for product in &mut products {
+            product.resolve_zone_name(&devices);
+        }
+
+        Ok(products)
+    }
+
+    /// Get the heating zones setup in the Hive account, with the Heating products which are
+    /// members of each zone.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use hive_client::authentication::{TrustedDevice, User};
+    ///
+    /// # tokio_test::block_on(async {
+    /// let client = hive_client::Client::new("Home Automation");
+    ///
+    /// let trusted_device = Some(TrustedDevice::new(
+    ///     "device_password",
+    ///     "device_group_key",
+    ///     "device_key"
+    /// ));
+    ///
+    /// client.login(User::new("example@example.com", "example"), trusted_device)
+    ///     .await
+    ///     .expect("Login should succeed");
+    ///
+    /// let zones = client.get_heating_zones()
+    ///     .await
+    ///     .expect("Heating zones should be retrieved");
+    ///
+    /// for zone in &zones {
+    ///     println!("{:?} is at {:?}°C", zone.name(), zone.current_temperature());
+    /// }
+    /// # })
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the list of products could not be retrieved.
+    pub async fn get_heating_zones(&self) -> Result<Vec<HeatingZone<'_>>, ApiError> {
+        Ok(HeatingZone::group(self.get_products().await?))
+    }
+
+    /// Set the target temperature of every Heating product (zone) in the Hive account.
+    ///
+    /// The target is applied to each of the Heating products concurrently, and the outcome
+    /// for each product is returned in the same order as they are returned by
+    /// [`Client::get_products`].
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use hive_client::authentication::{TrustedDevice, User};
+    ///
+    /// # tokio_test::block_on(async {
+    /// let client = hive_client::Client::new("Home Automation");
+    ///
+    /// let trusted_device = Some(TrustedDevice::new(
+    ///     "device_password",
+    ///     "device_group_key",
+    ///     "device_key"
+    /// ));
+    ///
+    /// client.login(User::new("example@example.com", "example"), trusted_device)
+    ///     .await
+    ///     .expect("Login should succeed");
+    ///
+    /// let results = client.set_all_heating_targets(20.0)
+    ///     .await
+    ///     .expect("Heating targets should be set");
+    ///
+    /// assert!(results.iter().all(|was_set| *was_set));
+    /// # })
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ApiError::InvalidTargetTemperature`] if the target is outside of the range
+    /// supported by Hive, otherwise an error if the list of products could not be retrieved.
+    ///
+    /// A failure to set the target on an individual product (including a target outside of the
+    /// narrower range a product may accept) is reported as `false` in its position in the
+    /// returned list.
+    pub async fn set_all_heating_targets(&self, celsius: f32) -> Result<Vec<bool>, ApiError> {
+        ensure_supported_target(celsius)?;
+
+        let mut products = self.get_products().await?;
+        let states = States(vec![State::TargetTemperature(celsius)]);
+
+        Ok(join_all(
+            products
+                .iter_mut()
+                .filter(|product| matches!(product.data, ProductData::Heating { .. }))
+                .map(|product| async {
+                    product.set_state(&states).await.unwrap_or_else(|err| {
+                        log::error!(target: target::API, "Failed to set the target temperature of a product: {err}");
+                        false
+                    })
+                }),
+        )
+        .await)
+    }
+
     /// Set a series of states on a product by a given ID.
     ///
     /// Wrapped by [`Product::set_state`] to set the states on a returned Product.
@@ -57,15 +278,133 @@ impl Client {
         &self,
         product_id: &str,
         r#type: &str,
-        states: States,
+        states: &States,
     ) -> Result<bool, ApiError> {
-        self.api
-            .set_product_state(
-                &*self.refresh_tokens_if_needed().await?,
-                product_id,
-                r#type,
-                states,
-            )
+        self.set_product_state_with_id(product_id, r#type, states, None)
             .await
+            .map(|set| set.value)
+    }
+
+    /// Set a series of states on a product, knowing only its ID and type - for example, from
+    /// a previous call to [`Client::get_products`] which has since been persisted.
+    ///
+    /// This skips retrieving the product first, so unlike [`Product::set_state`] a product
+    /// which is offline (or no longer exists) can't be detected before the change is sent.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use hive_client::authentication::{TrustedDevice, User};
+    /// use hive_client::products::{ProductType, State, States};
+    ///
+    /// # tokio_test::block_on(async {
+    /// let client = hive_client::Client::new("Home Automation");
+    ///
+    /// let trusted_device = Some(TrustedDevice::new(
+    ///     "device_password",
+    ///     "device_group_key",
+    ///     "device_key"
+    /// ));
+    ///
+    /// client.login(User::new("example@example.com", "example"), trusted_device)
+    ///     .await
+    ///     .expect("Login should succeed");
+    ///
+    /// client.set_product_state_by_id(
+    ///     "product-id",
+    ///     ProductType::Heating,
+    ///     &States(vec![State::TargetTemperature(18.0)]),
+    /// )
+    ///     .await
+    ///     .expect("Target temperature should be set");
+    /// # })
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ApiError::InvalidStateForProduct`] if any of the states are not applicable to
+    /// the type of product, otherwise an error if the states could not be set.
+    pub async fn set_product_state_by_id(
+        &self,
+        product_id: &str,
+        product_type: ProductType,
+        states: &States,
+    ) -> Result<bool, ApiError> {
+        product_type.ensure_applicable(states)?;
+
+        self.set_product_state(product_id, product_type.as_str(), states)
+            .await
+    }
+
+    /// Set a series of states on a product by a given ID, sending a correlation ID with the
+    /// request.
+    ///
+    /// Wrapped by [`Product::set_state_with_id`] to set the states on a returned Product.
+    pub(crate) async fn set_product_state_with_id(
+        &self,
+        product_id: &str,
+        r#type: &str,
+        states: &States,
+        correlation_id: Option<&str>,
+    ) -> Result<Correlated<bool>, ApiError> {
+        self.with_tokens(|tokens| async move {
+            self.api
+                .set_product_state(&tokens, product_id, r#type, states, correlation_id)
+                .await
+        })
+        .await
+    }
+
+    /// Set a series of states on a product by a given ID, returning the updated product data.
+    ///
+    /// Wrapped by [`Product::set_state_returning`] to set the states on a returned Product.
+    pub(crate) async fn set_product_state_returning(
+        &self,
+        product_id: &str,
+        r#type: &str,
+        states: &States,
+    ) -> Result<ProductData, ApiError> {
+        self.with_tokens(|tokens| async move {
+            self.api
+                .set_product_state_returning(&tokens, product_id, r#type, states)
+                .await
+        })
+        .await
+    }
+
+    /// Get the usage history of a product by a given ID, over a given date range.
+    ///
+    /// Wrapped by [`Product::get_usage`] to get the usage of a returned Product.
+    pub(crate) async fn get_product_usage(
+        &self,
+        product_id: &str,
+        r#type: &str,
+        range: Range<DateTime<Utc>>,
+    ) -> Result<UsageSeries, ApiError> {
+        self.with_tokens(|tokens| {
+            let range = range.clone();
+
+            async move {
+                self.api
+                    .get_product_usage(&tokens, product_id, r#type, range)
+                    .await
+            }
+        })
+        .await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_heating_targets_are_validated_before_any_are_set() {
+        let client = Client::new("Home Automation");
+
+        assert!(matches!(
+            client.set_all_heating_targets(40.0).await,
+            Err(ApiError::InvalidTargetTemperature(_))
+        ));
     }
 }
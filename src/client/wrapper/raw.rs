@@ -0,0 +1,88 @@
+use crate::{ApiError, Client};
+use serde_json::Value;
+
+impl Client {
+    /// Get the raw JSON response of an arbitrary path of the Hive API.
+    ///
+    /// The path is relative to the Hive API (for example, `"nodes"`), and the request is
+    /// authenticated in the same way as every other call made by the client - including
+    /// refreshing the tokens when they expire.
+    ///
+    /// **This is an advanced API.** It's an escape hatch for using endpoints which are yet to
+    /// be mapped by the crate, and offers no guarantees about the shape of the responses. Hive
+    /// may change or remove any endpoint without notice, and a typed equivalent should be
+    /// preferred where one exists.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use hive_client::authentication::{TrustedDevice, User};
+    ///
+    /// # tokio_test::block_on(async {
+    /// let client = hive_client::Client::new("Home Automation");
+    ///
+    /// let trusted_device = Some(TrustedDevice::new(
+    ///     "device_password",
+    ///     "device_group_key",
+    ///     "device_key"
+    /// ));
+    ///
+    /// client.login(User::new("example@example.com", "example"), trusted_device)
+    ///     .await
+    ///     .expect("Login should succeed");
+    ///
+    /// let nodes = client.get_raw("nodes")
+    ///     .await
+    ///     .expect("Nodes should be retrieved");
+    ///
+    /// println!("{nodes:#}");
+    /// # })
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the request failed, Hive responded with an unsuccessful status, or
+    /// the response was not valid JSON.
+    pub async fn get_raw(&self, path: &str) -> Result<Value, ApiError> {
+        self.with_tokens(|tokens| async move { self.api.get_raw(&tokens, path).await })
+            .await
+    }
+
+    /// Post a raw JSON body to an arbitrary path of the Hive API, returning the raw JSON
+    /// response.
+    ///
+    /// **This is an advanced API.** See [`Client::get_raw`].
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use hive_client::authentication::{TrustedDevice, User};
+    ///
+    /// # tokio_test::block_on(async {
+    /// let client = hive_client::Client::new("Home Automation");
+    ///
+    /// let trusted_device = Some(TrustedDevice::new(
+    ///     "device_password",
+    ///     "device_group_key",
+    ///     "device_key"
+    /// ));
+    ///
+    /// client.login(User::new("example@example.com", "example"), trusted_device)
+    ///     .await
+    ///     .expect("Login should succeed");
+    ///
+    /// let node = client.post_raw("nodes/heating/1234", &serde_json::json!({ "target": 18.0 }))
+    ///     .await
+    ///     .expect("Node should be updated");
+    /// # })
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the request failed, Hive responded with an unsuccessful status, or
+    /// the response was not valid JSON.
+    pub async fn post_raw(&self, path: &str, body: &Value) -> Result<Value, ApiError> {
+        self.with_tokens(|tokens| async move { self.api.post_raw(&tokens, path, body).await })
+            .await
+    }
+}
@@ -0,0 +1,115 @@
+use crate::{ApiError, Client, Scene, SceneEntry};
+use futures_util::future::join_all;
+
+impl Client {
+    /// Capture the current states of the given products as a [`Scene`], which can be saved and
+    /// later restored with [`Client::apply_scene`].
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use hive_client::authentication::{TrustedDevice, User};
+    ///
+    /// # tokio_test::block_on(async {
+    /// let client = hive_client::Client::new("Home Automation");
+    ///
+    /// let trusted_device = Some(TrustedDevice::new(
+    ///     "device_password",
+    ///     "device_group_key",
+    ///     "device_key"
+    /// ));
+    ///
+    /// client.login(User::new("example@example.com", "example"), trusted_device)
+    ///     .await
+    ///     .expect("Login should succeed");
+    ///
+    /// let scene = client.capture_scene(&["heating-product-id", "hot-water-product-id"])
+    ///     .await
+    ///     .expect("Scene should be captured");
+    ///
+    /// // ... later, put everything back the way it was.
+    /// client.apply_scene(&scene)
+    ///     .await
+    ///     .expect("Scene should be applied");
+    /// # })
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the list of products could not be retrieved, or if any of the
+    /// products could not be found (or are yet to be mapped by the crate).
+    pub async fn capture_scene(&self, product_ids: &[&str]) -> Result<Scene, ApiError> {
+        let products = self.get_products().await?;
+
+        let entries = product_ids
+            .iter()
+            .map(|product_id| {
+                products
+                    .iter()
+                    .find(|product| product.id() == *product_id)
+                    .and_then(SceneEntry::capture)
+                    .ok_or_else(|| ApiError::ProductNotFound((*product_id).to_string()))
+            })
+            .collect::<Result<_, _>>()?;
+
+        Ok(Scene { entries })
+    }
+
+    /// Apply a [`Scene`] to the Hive account, by setting the states of each of its products.
+    ///
+    /// Every entry in the scene is validated before any are applied. The entries are then
+    /// applied concurrently, and the outcome for each entry is returned in the same order as
+    /// [`Scene::entries`].
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use hive_client::authentication::{TrustedDevice, User};
+    /// use hive_client::Scene;
+    ///
+    /// # tokio_test::block_on(async {
+    /// let client = hive_client::Client::new("Home Automation");
+    ///
+    /// let trusted_device = Some(TrustedDevice::new(
+    ///     "device_password",
+    ///     "device_group_key",
+    ///     "device_key"
+    /// ));
+    ///
+    /// client.login(User::new("example@example.com", "example"), trusted_device)
+    ///     .await
+    ///     .expect("Login should succeed");
+    ///
+    /// let scene: Scene = serde_json::from_str(r#"{
+    ///     "entries": [
+    ///         { "product_id": "1234", "type": "heating", "states": { "mode": "MANUAL", "target": 21.0 } }
+    ///     ]
+    /// }"#).expect("Scene should be valid");
+    ///
+    /// let outcomes = client.apply_scene(&scene)
+    ///     .await
+    ///     .expect("Scene should be valid");
+    /// # })
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ApiError::InvalidStateForProduct`] if any entry in the scene has a state which
+    /// is not applicable to its type of product, or an error if any entry has a target
+    /// temperature outside of the range supported by Hive.
+    ///
+    /// A failure to apply an individual entry is reported in its position in the returned list.
+    pub async fn apply_scene(
+        &self,
+        scene: &Scene,
+    ) -> Result<Vec<Result<bool, ApiError>>, ApiError> {
+        for entry in &scene.entries {
+            entry.validate()?;
+        }
+
+        Ok(join_all(scene.entries.iter().map(|entry| {
+            self.set_product_state_by_id(&entry.product_id, entry.r#type, &entry.states)
+        }))
+        .await)
+    }
+}
@@ -0,0 +1,84 @@
+use crate::products::ProductChange;
+use crate::{ApiError, Client};
+use futures_util::{Stream, stream};
+use std::collections::{HashMap, VecDeque};
+use std::time::Duration;
+
+impl Client {
+    /// Subscribe to changes in the products setup in the Hive account.
+    ///
+    /// Hive does not offer a push channel for changes to products, so the products are polled
+    /// at the given interval, and a [`ProductChange`] is yielded for each product whose states
+    /// or key properties have changed since the previous poll. Every product is yielded on the
+    /// first poll.
+    ///
+    /// A failed poll is yielded as an error, and polling continues at the next interval. The
+    /// subscription ends when the stream is dropped.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use futures_util::StreamExt;
+    /// use hive_client::authentication::{TrustedDevice, User};
+    /// use std::time::Duration;
+    ///
+    /// # tokio_test::block_on(async {
+    /// let client = hive_client::Client::new("Home Automation");
+    ///
+    /// let trusted_device = Some(TrustedDevice::new(
+    ///     "device_password",
+    ///     "device_group_key",
+    ///     "device_key"
+    /// ));
+    ///
+    /// client.login(User::new("example@example.com", "example"), trusted_device)
+    ///     .await
+    ///     .expect("Login should succeed");
+    ///
+    /// let mut changes = Box::pin(client.subscribe(Duration::from_secs(60)));
+    ///
+    /// while let Some(change) = changes.next().await {
+    ///     match change {
+    ///         Ok(change) => println!("{:?} changed: {:?}", change.product, change.changed),
+    ///         Err(err) => eprintln!("Failed to poll the products: {err}"),
+    ///     }
+    /// }
+    /// # })
+    /// ```
+    pub fn subscribe(
+        &self,
+        interval: Duration,
+    ) -> impl Stream<Item = Result<ProductChange<'_>, ApiError>> + '_ {
+        struct Subscription<'a> {
+            seen: HashMap<String, HashMap<String, String>>,
+            pending: VecDeque<ProductChange<'a>>,
+            polled: bool,
+        }
+
+        let subscription = Subscription {
+            seen: HashMap::new(),
+            pending: VecDeque::new(),
+            polled: false,
+        };
+
+        stream::unfold(subscription, move |mut subscription| async move {
+            loop {
+                if let Some(change) = subscription.pending.pop_front() {
+                    return Some((Ok(change), subscription));
+                }
+
+                if subscription.polled {
+                    tokio::time::sleep(interval).await;
+                }
+                subscription.polled = true;
+
+                match Box::pin(self.get_products()).await {
+                    Ok(products) => subscription
+                        .pending
+                        .extend(ProductChange::diff(&mut subscription.seen, products)),
+                    Err(err) => return Some((Err(err), subscription)),
+                }
+            }
+        })
+    }
+}
@@ -1,5 +1,7 @@
-use crate::weather::Weather;
+use crate::helper::target;
+use crate::weather::{CachedWeather, Weather, WeatherOptions};
 use crate::{ApiError, Client};
+use reqwest::StatusCode;
 
 impl Client {
     /// Get the current weather according to Hive, for a given postcode.
@@ -35,10 +37,138 @@ impl Client {
     ///
     /// # Errors
     ///
-    /// Returns an error if the whether data could not be retrieved.
+    /// Returns an error if the whether data could not be retrieved, or
+    /// [`ApiError::WeatherUnavailable`] if Hive has no weather for the postcode.
     pub async fn get_weather(&self, postcode: &str) -> Result<Weather, ApiError> {
-        self.api
-            .get_weather(&*self.refresh_tokens_if_needed().await?, postcode)
+        self.get_weather_with_options(postcode, &WeatherOptions::default())
             .await
     }
+
+    /// Get the current weather according to Hive, for a given postcode, in a particular
+    /// set of units and language.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use hive_client::authentication::{TrustedDevice, User};
+    /// use hive_client::weather::{TemperatureUnit, WeatherOptions};
+    ///
+    /// # tokio_test::block_on(async {
+    /// let client = hive_client::Client::new("Home Automation");
+    ///
+    /// let trusted_device = Some(TrustedDevice::new(
+    ///     "device_password",
+    ///     "device_group_key",
+    ///     "device_key"
+    /// ));
+    ///
+    /// client.login(User::new("example@example.com", "example"), trusted_device)
+    ///     .await
+    ///     .expect("Login should succeed");
+    ///
+    /// let options = WeatherOptions {
+    ///     units: Some(TemperatureUnit::Fahrenheit),
+    ///     language: Some("fr-FR".to_string()),
+    /// };
+    ///
+    /// let weather = client.get_weather_with_options("SW1A 1AA", &options)
+    ///     .await
+    ///     .expect("Weather should be retrieved");
+    ///
+    /// println!("The current weather is: {}", weather.data.description);
+    /// # })
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the whether data could not be retrieved, or
+    /// [`ApiError::WeatherUnavailable`] if Hive has no weather for the postcode.
+    pub async fn get_weather_with_options(
+        &self,
+        postcode: &str,
+        options: &WeatherOptions,
+    ) -> Result<Weather, ApiError> {
+        self.with_tokens(
+            |tokens| async move { self.api.get_weather(&tokens, postcode, options).await },
+        )
+        .await
+    }
+
+    /// Get the current weather according to Hive, for a given postcode, falling back to the
+    /// last known weather for the postcode if it could not be fetched.
+    ///
+    /// Useful for ambient displays, where a brief network failure shouldn't blank the weather
+    /// entirely.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use hive_client::authentication::{TrustedDevice, User};
+    /// use hive_client::weather::CachedWeather;
+    ///
+    /// # tokio_test::block_on(async {
+    /// let client = hive_client::Client::new("Home Automation");
+    ///
+    /// let trusted_device = Some(TrustedDevice::new(
+    ///     "device_password",
+    ///     "device_group_key",
+    ///     "device_key"
+    /// ));
+    ///
+    /// client.login(User::new("example@example.com", "example"), trusted_device)
+    ///     .await
+    ///     .expect("Login should succeed");
+    ///
+    /// let CachedWeather { weather, is_stale } = client.get_weather_or_last_known("SW1A 1AA")
+    ///     .await
+    ///     .expect("Weather should be retrieved");
+    ///
+    /// println!("The current temperature is: {} (stale: {is_stale})", weather.data.temperature);
+    /// # })
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the whether data could not be retrieved, and there is no previously
+    /// retrieved weather for the postcode to fall back to.
+    pub async fn get_weather_or_last_known(
+        &self,
+        postcode: &str,
+    ) -> Result<CachedWeather, ApiError> {
+        match self.get_weather(postcode).await {
+            Ok(weather) => {
+                self.last_known_weather
+                    .lock()
+                    .await
+                    .insert(postcode.to_string(), weather.clone());
+
+                Ok(CachedWeather {
+                    weather,
+                    is_stale: false,
+                })
+            }
+            Err(
+                error @ (ApiError::RequestError(_)
+                | ApiError::UnexpectedStatus(
+                    StatusCode::BAD_GATEWAY
+                    | StatusCode::SERVICE_UNAVAILABLE
+                    | StatusCode::GATEWAY_TIMEOUT,
+                )),
+            ) => {
+                let last_known_weather = self.last_known_weather.lock().await;
+
+                last_known_weather
+                    .get(postcode)
+                    .map_or(Err(error), |weather| {
+                        log::warn!(target: target::API, "Unable to fetch weather, falling back to last known weather.");
+
+                        Ok(CachedWeather {
+                            weather: weather.clone(),
+                            is_stale: true,
+                        })
+                    })
+            }
+            Err(error) => Err(error),
+        }
+    }
 }
@@ -1 +1,5 @@
+pub mod redact;
+pub mod target;
+pub mod temperature;
+pub mod timestamp;
 pub mod url;
@@ -0,0 +1,14 @@
+use std::fmt;
+use std::fmt::{Debug, Formatter};
+
+/// A secret value, which only reveals its length when formatted with [`Debug`].
+///
+/// This is used in place of secrets (tokens, passwords, etc) in `Debug` implementations, so
+/// that they aren't leaked into logs or panic messages.
+pub struct Redacted<'a>(pub &'a str);
+
+impl Debug for Redacted<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "<redacted {} chars>", self.0.len())
+    }
+}
@@ -0,0 +1,11 @@
+//! The targets which the crate's `log` messages are emitted under.
+//!
+//! Every message is emitted under a sub-target of `hive_client`, so the crate's logging can be
+//! configured separately from the rest of an application (for example, with
+//! `RUST_LOG=hive_client=debug`).
+
+/// Messages about authenticating with Hive, and refreshing tokens.
+pub const AUTH: &str = "hive_client::auth";
+
+/// Messages about requests made to the Hive API.
+pub const API: &str = "hive_client::api";
@@ -0,0 +1,28 @@
+/// Convert a temperature in Fahrenheit to Celsius.
+pub fn fahrenheit_to_celsius(fahrenheit: f32) -> f32 {
+    (fahrenheit - 32.0) * 5.0 / 9.0
+}
+
+/// Convert a temperature in Celsius to Fahrenheit.
+pub fn celsius_to_fahrenheit(celsius: f32) -> f32 {
+    celsius.mul_add(9.0 / 5.0, 32.0)
+}
+
+/// Round a temperature to the nearest half degree, which is the precision Hive accepts
+/// target temperatures in.
+pub fn round_to_half_degree(temperature: f32) -> f32 {
+    (temperature * 2.0).round() / 2.0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_conversions_round_trip() {
+        assert!((fahrenheit_to_celsius(68.0) - 20.0).abs() < f32::EPSILON);
+        assert!((celsius_to_fahrenheit(20.0) - 68.0).abs() < f32::EPSILON);
+        assert!((round_to_half_degree(fahrenheit_to_celsius(70.0)) - 21.0).abs() < f32::EPSILON);
+        assert!((round_to_half_degree(fahrenheit_to_celsius(66.0)) - 19.0).abs() < f32::EPSILON);
+    }
+}
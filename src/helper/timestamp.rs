@@ -0,0 +1,136 @@
+//! Tolerant deserialization of the timestamps reported by Hive, for use with
+//! `#[serde(with = "...")]`.
+//!
+//! Hive reports timestamps as milliseconds since the Unix epoch, however some firmware reports
+//! them in seconds, or as numeric strings. Timestamps are always serialized in milliseconds.
+
+use chrono::serde::{ts_milliseconds, ts_milliseconds_option};
+use chrono::{DateTime, Utc};
+use serde::de::Error;
+use serde::{Deserialize, Deserializer, Serializer};
+
+/// Timestamps with a magnitude below this are taken to be in seconds, rather than milliseconds.
+///
+/// In milliseconds this is in 1973, whereas in seconds it is thousands of years in the future,
+/// so there's no overlap between the timestamps Hive could report in either unit.
+const SECONDS_THRESHOLD: i64 = 100_000_000_000;
+
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum Timestamp {
+    Integer(i64),
+    Float(f64),
+    String(String),
+}
+
+impl Timestamp {
+    fn parse<E: Error>(self) -> Result<DateTime<Utc>, E> {
+        #[allow(clippy::cast_possible_truncation)]
+        let value = match self {
+            Self::Integer(value) => value,
+            Self::Float(value) if value.is_finite() => value as i64,
+            Self::String(value) => value
+                .trim()
+                .parse()
+                .map_err(|_| E::custom(format!("invalid timestamp {value:?}")))?,
+            Self::Float(value) => return Err(E::custom(format!("invalid timestamp {value}"))),
+        };
+
+        let millis = if value.abs() < SECONDS_THRESHOLD {
+            value.checked_mul(1000)
+        } else {
+            Some(value)
+        };
+
+        millis
+            .and_then(DateTime::from_timestamp_millis)
+            .ok_or_else(|| E::custom(format!("timestamp {value} is out of range")))
+    }
+}
+
+/// Serialize a timestamp in milliseconds.
+pub fn serialize<S: Serializer>(
+    timestamp: &DateTime<Utc>,
+    serializer: S,
+) -> Result<S::Ok, S::Error> {
+    ts_milliseconds::serialize(timestamp, serializer)
+}
+
+/// Deserialize a timestamp in either milliseconds or seconds.
+pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<DateTime<Utc>, D::Error> {
+    Timestamp::deserialize(deserializer)?.parse()
+}
+
+/// Tolerant deserialization of optional timestamps, where `null` is `None`.
+pub mod option {
+    use super::{Timestamp, ts_milliseconds_option};
+    use chrono::{DateTime, Utc};
+    use serde::{Deserialize, Deserializer, Serializer};
+
+    /// Serialize an optional timestamp in milliseconds.
+    // The signature is dictated by `#[serde(with = "...")]`.
+    #[allow(clippy::ref_option)]
+    pub fn serialize<S: Serializer>(
+        timestamp: &Option<DateTime<Utc>>,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        ts_milliseconds_option::serialize(timestamp, serializer)
+    }
+
+    /// Deserialize an optional timestamp in either milliseconds or seconds.
+    pub fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<Option<DateTime<Utc>>, D::Error> {
+        Option::<Timestamp>::deserialize(deserializer)?
+            .map(Timestamp::parse)
+            .transpose()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde::Serialize;
+
+    #[derive(Serialize, Deserialize)]
+    struct Example {
+        #[serde(with = "super")]
+        at: DateTime<Utc>,
+
+        #[serde(default, with = "super::option")]
+        seen: Option<DateTime<Utc>>,
+    }
+
+    #[test]
+    fn test_timestamps_in_milliseconds_or_seconds() {
+        let expected = DateTime::from_timestamp(1_700_000_000, 0);
+
+        for at in [
+            "1700000000000",
+            "1700000000",
+            "1700000000000.0",
+            r#""1700000000""#,
+        ] {
+            let example: Example = serde_json::from_str(&format!(r#"{{ "at": {at} }}"#))
+                .expect("Timestamp should deserialize");
+
+            assert_eq!(Some(example.at), expected, "{at}");
+            assert_eq!(example.seen, None);
+        }
+
+        let example: Example =
+            serde_json::from_str(r#"{ "at": 0, "seen": null }"#).expect("Null should be None");
+        assert_eq!(example.seen, None);
+
+        assert_eq!(
+            serde_json::to_string(&Example {
+                at: expected.expect("Timestamp should be valid"),
+                seen: expected,
+            })
+            .expect("Timestamp should serialize"),
+            r#"{"at":1700000000000,"seen":1700000000000}"#
+        );
+
+        assert!(serde_json::from_str::<Example>(r#"{ "at": null }"#).is_err());
+    }
+}
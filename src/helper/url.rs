@@ -18,7 +18,14 @@ pub enum Url<'a> {
         activate: bool,
     },
     Device,
+    History {
+        r#type: &'a str,
+        id: &'a str,
+    },
     Weather,
+    Raw {
+        path: &'a str,
+    },
 }
 
 pub fn get_base_url(url: &Url<'_>) -> String {
@@ -49,6 +56,16 @@ pub fn get_base_url(url: &Url<'_>) -> String {
         Url::Node { .. } => {
             format!("{}/{}", BEEKEEPER_BASE_URL, "nodes")
         }
+        Url::History { r#type, id } => {
+            format!("{}/{}/{}/{}", BEEKEEPER_BASE_URL, "history", r#type, id)
+        }
+
+        /*
+         * Arbitrary endpoints, requested directly by the caller
+         */
+        Url::Raw { path } => {
+            format!("{}/{}", BEEKEEPER_BASE_URL, path.trim_start_matches('/'))
+        }
 
         /*
          * Weather endpoint
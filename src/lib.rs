@@ -17,6 +17,31 @@
 //! hive-client = "0.0.6"
 //! ```
 //!
+//! ## Features
+//!
+//! - `blocking` - A blocking (synchronous) client, in the `blocking` module, for use outside of
+//!   an async runtime.
+//! - `zeroize` - Overwrite the passwords and tokens held by `User`, `TrustedDevice`, the
+//!   authentication tokens and `SessionExport` when they are dropped, so credentials don't
+//!   linger in memory once they're no longer needed. The copies of the passwords held by the
+//!   SRP clients used to authenticate with Hive (for as long as the client is logged in) are
+//!   not covered.
+//! - `fixtures` - Serve a client from recorded JSON responses (with `Client::from_fixtures`) in
+//!   place of a live Hive account, recording any changes instead of sending them - for
+//!   deterministic tests and offline demos. Example responses are in the `fixtures` directory.
+//!
+//! ## Logging
+//!
+//! The crate emits messages through the [`log`](https://docs.rs/log) facade, under the
+//! `hive_client` target so they can be filtered separately from the rest of an application:
+//!
+//! - `hive_client::auth` - Logging in, responding to challenges and refreshing tokens.
+//! - `hive_client::api` - Requests made to the Hive API.
+//!
+//! For example, with [`env_logger`](https://docs.rs/env_logger), `RUST_LOG=hive_client=debug`
+//! enables all of the crate's messages, while `RUST_LOG=hive_client::auth=warn` only enables the
+//! authentication warnings and errors. Tokens, passwords and challenge codes are never logged.
+//!
 //! ## Capabilities
 //!
 //! 1. Authenticate with Hive.
@@ -91,7 +116,7 @@
 //!         .expect("Products should be retrieved");
 //!
 //!     if let Some(mut heating) = products.into_iter().find(|Product { data, .. }| matches!(data, ProductData::Heating { .. })) {
-//!         let was_set = heating.set_state(States(vec!(State::TargetTemperature(18.0))))
+//!         let was_set = heating.set_state(&States(vec!(State::TargetTemperature(18.0))))
 //!             .await
 //!             .expect("Product state should be set");
 //!         # assert!(was_set);
@@ -184,6 +209,8 @@
 //! cd infrastructure/tests && terraform destroy
 //! ```
 
+#[cfg(feature = "blocking")]
+pub mod blocking;
 mod client;
 mod constants;
 mod helper;
@@ -60,3 +60,42 @@ pub async fn test_listing_operations() {
 
     client.logout().await;
 }
+
+#[cfg(feature = "fixtures")]
+#[tokio::test]
+pub async fn test_concurrent_clients() {
+    use hive_client::fixtures::RecordedCall;
+    use hive_client::products::{Mode, State, States};
+
+    let first = Client::from_fixtures("fixtures");
+    let second = Client::from_fixtures("fixtures");
+
+    // Each client holds its own state, so both should be able to list products in parallel
+    // without interfering with each other.
+    let (first_products, second_products) =
+        tokio::join!(first.get_products(), second.get_products());
+
+    let mut first_products = first_products.expect("Listing products should succeed");
+    let second_products = second_products.expect("Listing products should succeed");
+
+    assert!(!first_products.is_empty(), "Products should not be empty");
+    assert_eq!(
+        first_products.len(),
+        second_products.len(),
+        "Both clients should see the same products"
+    );
+
+    first_products[0]
+        .set_state(&States(vec![State::Mode(Mode::Off)]))
+        .await
+        .expect("State change should be recorded");
+
+    assert!(matches!(
+        first.recorded_calls().as_slice(),
+        [RecordedCall::SetState { .. }]
+    ));
+    assert!(
+        second.recorded_calls().is_empty(),
+        "Changes made through one client should not be seen by the other"
+    );
+}